@@ -180,6 +180,7 @@ fn testnet_genesis(
 		},
 		sys_man: SysManConfig {
 			sys_man: sys_man.iter().map(|x| (x.0.clone(), x.1.clone())).collect(),
+			metadata_schemas: Default::default(),
 		},
 		aura: AuraConfig {
 			authorities: initial_authorities.iter().map(|x| (x.0.clone())).collect(),
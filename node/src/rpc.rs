@@ -7,7 +7,7 @@
 
 use std::sync::Arc;
 
-use scv_node::{opaque::Block, AccountId, Balance, Index};
+use scv_node::{opaque::Block, AccountId, Balance, Index, Runtime};
 pub use sc_rpc_api::DenyUnsafe;
 use sc_transaction_pool_api::TransactionPool;
 use sp_api::ProvideRuntimeApi;
@@ -32,9 +32,16 @@ where
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+	C::Api: pallet_sys_man_rpc::SysManRuntimeApi<
+		Block,
+		AccountId,
+		pallet_sys_man::SysManAccount<Runtime>,
+		pallet_sys_man::AuditEntryOf<Runtime>,
+	>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + 'static,
 {
+	use pallet_sys_man_rpc::{SysMan, SysManApi};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
 	use substrate_frame_rpc_system::{FullSystem, SystemApi};
 
@@ -45,6 +52,8 @@ where
 
 	io.extend_with(TransactionPaymentApi::to_delegate(TransactionPayment::new(client.clone())));
 
+	io.extend_with(SysManApi::to_delegate(SysMan::new(client)));
+
 	// Extend this RPC with a custom API by using the following syntax.
 	// `YourRpcStruct` should have a reference to a client, which is needed
 	// to call into the runtime.
@@ -16,8 +16,15 @@ pub mod pallet {
 	use frame_support::inherent::Vec;
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
-	use pallet_utils::{Role, Status};
+	use pallet_utils::{
+		traits::{
+			AccountRegistry, AccountResolver, OnAccountErased, OnAccountRebound,
+			OnAccountStatusChange, SysManAuthority,
+		},
+		KeyPermission, Role, Status,
+	};
 	use scale_info::TypeInfo;
+	use sp_runtime::traits::{Hash, Saturating};
 
 	#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
 	#[scale_info(bounds(), skip_type_params(T))]
@@ -28,11 +35,46 @@ pub mod pallet {
 		metadata: Vec<u8>,
 	}
 
+	/// Guardians able to move an account to a new key if its owner loses the current one.
+	#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	#[scale_info(bounds(), skip_type_params(T))]
+	pub struct RecoveryConfig<T: Config> {
+		pub guardians: Vec<T::AccountId>,
+		pub threshold: u32,
+		pub delay: T::BlockNumber,
+	}
+
+	/// Recovery of an account to `new_key`, pending guardian approvals and the delay.
+	#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	#[scale_info(bounds(), skip_type_params(T))]
+	pub struct ActiveRecovery<T: Config> {
+		pub new_key: T::AccountId,
+		pub approvals: Vec<T::AccountId>,
+		pub started: T::BlockNumber,
+	}
+
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// Source of sys-man approval for the `SysMan` and `Organization` roles.
+		type SysManAuthority: SysManAuthority<Self::AccountId>;
+		/// Pallets to notify when an account is erased.
+		type OnAccountErased: OnAccountErased<Self::AccountId>;
+		/// Pallets to notify when an account is deactivated or reactivated.
+		type OnAccountStatusChange: OnAccountStatusChange<Self::AccountId>;
+		/// Pallets to notify when an account is recovered to a new key.
+		type OnAccountRebound: OnAccountRebound<Self::AccountId>;
+		/// Minimum number of blocks the owner has to cancel a recovery of its account.
+		#[pallet::constant]
+		type MinRecoveryDelay: Get<Self::BlockNumber>;
+		/// Maximum number of guardians of a single account.
+		#[pallet::constant]
+		type MaxGuardians: Get<u32>;
+		/// Maximum number of secondary keys linked to a single account.
+		#[pallet::constant]
+		type MaxLinkedKeys: Get<u32>;
 	}
 
 	#[pallet::pallet]
@@ -50,17 +92,111 @@ pub mod pallet {
 
 	#[pallet::storage]
 	#[pallet::getter(fn account_role)]
-	// Learn more about declaring storage items:
-	// https://docs.substrate.io/v3/runtime/storage#declaring-storage-items
+	/// Role granted to an account. This is a snapshot: a role revoked or suspended in sys-man
+	/// stays here until someone calls `demote_role`, so check sys-man for anything privileged.
 	pub type AccountRole<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, Role, OptionQuery>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn erased_account)]
+	/// Hash of the records of erased accounts, kept in place of their personal data.
+	pub type ErasedAccounts<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, T::Hash, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn recovery_config)]
+	/// Guardians nominated by an account.
+	pub type RecoveryConfigs<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, RecoveryConfig<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn active_recovery)]
+	/// Recoveries in progress, keyed by the account being recovered.
+	pub type ActiveRecoveries<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, ActiveRecovery<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn linked_key)]
+	/// Primary account and permission of each secondary key.
+	pub type LinkedKeys<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, (T::AccountId, KeyPermission), OptionQuery>;
+
+	#[pallet::storage]
+	/// Secondary keys indexed by their primary account.
+	pub type KeysByAccount<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		T::AccountId,
+		(),
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn linked_key_cnt)]
+	/// Number of secondary keys linked to an account.
+	pub type LinkedKeyCnt<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn pending_link)]
+	/// Links offered by a primary account, waiting for the secondary key to accept. Keyed by
+	/// primary account and key, so offers of different accounts don't replace each other.
+	pub type PendingLinks<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		T::AccountId,
+		KeyPermission,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn pending_role)]
+	/// Privileged roles requested at registration, waiting for a system manager to confirm.
+	pub type PendingRole<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, Role, OptionQuery>;
+
 	// Pallets use events to inform users when important changes are made.
 	// https://docs.substrate.io/v3/runtime/events-and-errors
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		AccountRegisted,
-		AccountUpdated(T::AccountId),
+		/// An account changed its role or metadata.
+		/// [who, old_role, new_role, old_metadata, new_metadata]
+		AccountUpdated(T::AccountId, Role, Role, Vec<u8>, Vec<u8>),
+		/// A privileged role is waiting for confirmation. [who, role]
+		RoleRequested(T::AccountId, Role),
+		/// A system manager confirmed a requested role. [who, role, confirmed_by]
+		RoleConfirmed(T::AccountId, Role, T::AccountId),
+		/// A system manager rejected a requested role. [who, role, rejected_by]
+		RoleRejected(T::AccountId, Role, T::AccountId),
+		/// A role no longer approved in sys-man was taken away. [who, role]
+		RoleWithdrawn(T::AccountId, Role),
+		/// An account was deactivated by its owner. [who]
+		AccountDeactivated(T::AccountId),
+		/// A deactivated account was reactivated by its owner. [who]
+		AccountReactivated(T::AccountId),
+		/// An account was erased by its owner. [who, tombstone]
+		AccountErased(T::AccountId, T::Hash),
+		/// An account nominated its guardians. [who, threshold]
+		RecoveryConfigured(T::AccountId, u32),
+		/// A guardian started to recover an account. [lost, new_key, guardian]
+		RecoveryInitiated(T::AccountId, T::AccountId, T::AccountId),
+		/// A guardian approved the recovery of an account. [lost, guardian]
+		RecoveryApproved(T::AccountId, T::AccountId),
+		/// The owner of an account cancelled its recovery. [lost]
+		RecoveryCancelled(T::AccountId),
+		/// An account was moved to a new key. [lost, new_key]
+		AccountRecovered(T::AccountId, T::AccountId),
+		/// An account offered to link a secondary key. [primary, key, permission]
+		KeyLinkRequested(T::AccountId, T::AccountId, KeyPermission),
+		/// A secondary key accepted to act for an account. [primary, key, permission]
+		KeyLinked(T::AccountId, T::AccountId, KeyPermission),
+		/// The permission of a secondary key changed. [primary, key, permission]
+		KeyPermissionChanged(T::AccountId, T::AccountId, KeyPermission),
+		/// A secondary key was unlinked from an account. [primary, key]
+		KeyUnlinked(T::AccountId, T::AccountId),
 	}
 
 	// Errors inform users that something went wrong.
@@ -70,6 +206,54 @@ pub mod pallet {
 		AlreadyRegistered,
 		/// Account is not Registered
 		AccountNotRegistered,
+		/// Privileged roles can only be granted through sys-man approval
+		RoleNotAllowed,
+		/// Account has not been approved for the role in sys-man
+		RoleNotApproved,
+		/// Caller is not an active system manager
+		NotSysManAuthority,
+		/// Account has no role waiting for confirmation
+		NoPendingRole,
+		/// Account is deactivated
+		AccountNotActive,
+		/// Account is not deactivated
+		AccountNotDeactivated,
+		/// Account has been erased and can not be registered again
+		AccountErased,
+		/// Guardians must be distinct and must not include the account itself
+		InvalidGuardians,
+		/// Too many guardians
+		TooManyGuardians,
+		/// Threshold must be between one and the number of guardians
+		InvalidThreshold,
+		/// Account has not nominated any guardians
+		RecoveryNotConfigured,
+		/// Caller is not a guardian of the account
+		NotGuardian,
+		/// A recovery of the account is already in progress
+		RecoveryInProgress,
+		/// No recovery of the account is in progress
+		RecoveryNotStarted,
+		/// Guardian already approved the recovery
+		AlreadyApproved,
+		/// Not enough guardians approved the recovery
+		NotEnoughApprovals,
+		/// The recovery delay has not passed yet
+		RecoveryDelayNotPassed,
+		/// The recovery delay is shorter than `MinRecoveryDelay`
+		RecoveryDelayTooShort,
+		/// The new key is already in use
+		NewKeyInUse,
+		/// Secondary keys can not act as a primary account
+		NotPrimaryKey,
+		/// Too many secondary keys linked to the account
+		TooManyLinkedKeys,
+		/// No link from the account is waiting for this key
+		NoPendingLink,
+		/// Key is not linked to the account
+		KeyNotLinked,
+		/// The role of the account is still approved in sys-man
+		RoleStillApproved,
 	}
 
 	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
@@ -85,40 +269,448 @@ pub mod pallet {
 			// This function will return an error if the extrinsic is not signed.
 			// https://docs.substrate.io/v3/runtime/origins
 			let who = ensure_signed(origin)?;
+			ensure!(!<ErasedAccounts<T>>::contains_key(&who), Error::<T>::AccountErased);
+			ensure!(!<LinkedKeys<T>>::contains_key(&who), Error::<T>::NotPrimaryKey);
+			// privileged roles not yet approved in sys-man start out as `User`
+			let granted = if T::SysManAuthority::has_role(&who, &role) { role } else { Role::User };
 			match <AccountStorage<T>>::try_get(&who) {
 				Err(_) => {
 					<AccountStorage<T>>::insert(
 						&who,
 						Account {
 							id: who.clone(),
-							role: role.clone(),
+							role: granted.clone(),
 							status: Status::Active,
 							metadata,
 						},
 					);
-					<AccountRole<T>>::insert(who, role.clone());
+					<AccountRole<T>>::insert(&who, granted.clone());
 				},
 				Ok(_) => Err(Error::<T>::AlreadyRegistered)?,
 			}
 			// Return a successful DispatchResultWithPostInfo
 			Self::deposit_event(Event::AccountRegisted);
+			if granted != role {
+				<PendingRole<T>>::insert(&who, role.clone());
+				Self::deposit_event(Event::RoleRequested(who, role));
+			}
 			Ok(())
 		}
-		// TODO
+		/// Change the role and metadata of the signer's account. The role can only be kept or
+		/// dropped to `User`, privileged roles are never self-assigned.
 		#[pallet::weight(10_000)]
-		pub fn update(origin: OriginFor<T>, _role: Role, _metadata: Vec<u8>) -> DispatchResult {
+		pub fn update(origin: OriginFor<T>, role: Role, metadata: Vec<u8>) -> DispatchResult {
 			// Check that the extrinsic was signed and get the signer.
 			// This function will return an error if the extrinsic is not signed.
 			// https://docs.substrate.io/v3/runtime/origins
 			let who = ensure_signed(origin)?;
-			match <AccountStorage<T>>::try_get(&who) {
-				Ok(_) => {
-					Self::deposit_event(Event::AccountUpdated(who));
-					// Return a successful DispatchResultWithPostInfo
-					Ok(())
+			let mut account =
+				<AccountStorage<T>>::try_get(&who).map_err(|_| Error::<T>::AccountNotRegistered)?;
+
+			ensure!(account.status == Status::Active, Error::<T>::AccountNotActive);
+			ensure!(role == account.role || role == Role::User, Error::<T>::RoleNotAllowed);
+
+			let old_role = core::mem::replace(&mut account.role, role.clone());
+			let old_metadata = core::mem::replace(&mut account.metadata, metadata.clone());
+
+			<AccountStorage<T>>::insert(&who, account);
+			<AccountRole<T>>::insert(&who, role.clone());
+
+			Self::deposit_event(Event::AccountUpdated(who, old_role, role, old_metadata, metadata));
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+
+		/// Grant the role an account requested at registration. Must be called by an active
+		/// system manager, once the account has been approved for the role in sys-man.
+		#[pallet::weight(10_000)]
+		pub fn confirm_role(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(T::SysManAuthority::is_authority(&sender), Error::<T>::NotSysManAuthority);
+			let role = <PendingRole<T>>::get(&who).ok_or(Error::<T>::NoPendingRole)?;
+			ensure!(T::SysManAuthority::has_role(&who, &role), Error::<T>::RoleNotApproved);
+			let mut account =
+				<AccountStorage<T>>::try_get(&who).map_err(|_| Error::<T>::AccountNotRegistered)?;
+
+			account.role = role.clone();
+
+			<AccountStorage<T>>::insert(&who, account);
+			<AccountRole<T>>::insert(&who, role.clone());
+			<PendingRole<T>>::remove(&who);
+
+			Self::deposit_event(Event::RoleConfirmed(who, role, sender));
+			Ok(())
+		}
+
+		/// Drop the role an account requested at registration, leaving it a `User`. Must be
+		/// called by an active system manager.
+		#[pallet::weight(10_000)]
+		pub fn reject_role(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(T::SysManAuthority::is_authority(&sender), Error::<T>::NotSysManAuthority);
+			let role = <PendingRole<T>>::take(&who).ok_or(Error::<T>::NoPendingRole)?;
+
+			Self::deposit_event(Event::RoleRejected(who, role, sender));
+			Ok(())
+		}
+
+		/// Drop the role of an account back to `User` once sys-man no longer approves it, e.g.
+		/// after a revocation or suspension. Anyone can call this.
+		#[pallet::weight(10_000)]
+		pub fn demote_role(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			ensure_signed(origin)?;
+			let mut account =
+				<AccountStorage<T>>::try_get(&who).map_err(|_| Error::<T>::AccountNotRegistered)?;
+			ensure!(
+				!T::SysManAuthority::has_role(&who, &account.role),
+				Error::<T>::RoleStillApproved
+			);
+
+			let role = core::mem::replace(&mut account.role, Role::User);
+
+			<AccountStorage<T>>::insert(&who, account);
+			<AccountRole<T>>::insert(&who, Role::User);
+
+			Self::deposit_event(Event::RoleWithdrawn(who, role));
+			Ok(())
+		}
+
+		/// Deactivate the signer's account. Its data is kept, but it can no longer be updated
+		/// and pallets holding data linked to it are notified to hide it.
+		#[pallet::weight(10_000)]
+		pub fn deactivate(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut account =
+				<AccountStorage<T>>::try_get(&who).map_err(|_| Error::<T>::AccountNotRegistered)?;
+			ensure!(account.status == Status::Active, Error::<T>::AccountNotActive);
+
+			account.status = Status::Deactivated;
+
+			<AccountStorage<T>>::insert(&who, account);
+
+			T::OnAccountStatusChange::on_account_deactivated(&who);
+
+			Self::deposit_event(Event::AccountDeactivated(who));
+			Ok(())
+		}
+
+		/// Reactivate the signer's deactivated account, showing the data linked to it again.
+		#[pallet::weight(10_000)]
+		pub fn reactivate(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut account =
+				<AccountStorage<T>>::try_get(&who).map_err(|_| Error::<T>::AccountNotRegistered)?;
+			ensure!(account.status == Status::Deactivated, Error::<T>::AccountNotDeactivated);
+
+			account.status = Status::Active;
+
+			<AccountStorage<T>>::insert(&who, account);
+
+			T::OnAccountStatusChange::on_account_reactivated(&who);
+
+			Self::deposit_event(Event::AccountReactivated(who));
+			Ok(())
+		}
+
+		/// Erase the signer's account, keeping only a hash of its record as a tombstone. Pallets
+		/// holding data linked to the account are notified to scrub it.
+		#[pallet::weight(Pallet::<T>::erase_weight(
+			T::MaxLinkedKeys::get(),
+			T::OnAccountErased::max_erase_weight()
+		))]
+		pub fn erase(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let account =
+				<AccountStorage<T>>::try_get(&who).map_err(|_| Error::<T>::AccountNotRegistered)?;
+			let tombstone = T::Hashing::hash_of(&account);
+
+			<AccountStorage<T>>::remove(&who);
+			<AccountRole<T>>::remove(&who);
+			<PendingRole<T>>::remove(&who);
+			<RecoveryConfigs<T>>::remove(&who);
+			<ActiveRecoveries<T>>::remove(&who);
+			let mut keys = 0;
+			for (key, _) in <KeysByAccount<T>>::drain_prefix(&who) {
+				<LinkedKeys<T>>::remove(&key);
+				keys += 1;
+			}
+			<LinkedKeyCnt<T>>::remove(&who);
+			<ErasedAccounts<T>>::insert(&who, tombstone);
+
+			let hooks = T::OnAccountErased::on_account_erased(&who);
+
+			Self::deposit_event(Event::AccountErased(who, tombstone));
+			Ok(Some(Self::erase_weight(keys, hooks)).into())
+		}
+
+		/// Nominate the guardians able to recover the signer's account, how many of them have to
+		/// approve a recovery and how many blocks, at least `MinRecoveryDelay`, the owner has to
+		/// cancel it.
+		#[pallet::weight(10_000)]
+		pub fn set_guardians(
+			origin: OriginFor<T>,
+			guardians: Vec<T::AccountId>,
+			threshold: u32,
+			delay: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(<AccountStorage<T>>::contains_key(&who), Error::<T>::AccountNotRegistered);
+			ensure!(!<ActiveRecoveries<T>>::contains_key(&who), Error::<T>::RecoveryInProgress);
+			ensure!(
+				guardians.len() <= T::MaxGuardians::get() as usize,
+				Error::<T>::TooManyGuardians
+			);
+
+			let mut sorted = guardians.clone();
+			sorted.sort();
+			sorted.dedup();
+			ensure!(
+				sorted.len() == guardians.len() && !guardians.contains(&who),
+				Error::<T>::InvalidGuardians
+			);
+			ensure!(
+				threshold > 0 && threshold as usize <= guardians.len(),
+				Error::<T>::InvalidThreshold
+			);
+			ensure!(delay >= T::MinRecoveryDelay::get(), Error::<T>::RecoveryDelayTooShort);
+
+			<RecoveryConfigs<T>>::insert(&who, RecoveryConfig { guardians, threshold, delay });
+
+			Self::deposit_event(Event::RecoveryConfigured(who, threshold));
+			Ok(())
+		}
+
+		/// Start moving `lost` to `new_key`. Must be called by one of its guardians, whose
+		/// approval is counted.
+		#[pallet::weight(10_000)]
+		pub fn initiate_recovery(
+			origin: OriginFor<T>,
+			lost: T::AccountId,
+			new_key: T::AccountId,
+		) -> DispatchResult {
+			let guardian = ensure_signed(origin)?;
+			let config =
+				<RecoveryConfigs<T>>::get(&lost).ok_or(Error::<T>::RecoveryNotConfigured)?;
+			ensure!(config.guardians.contains(&guardian), Error::<T>::NotGuardian);
+			ensure!(!<ActiveRecoveries<T>>::contains_key(&lost), Error::<T>::RecoveryInProgress);
+			Self::ensure_unused_key(&new_key)?;
+
+			<ActiveRecoveries<T>>::insert(
+				&lost,
+				ActiveRecovery {
+					new_key: new_key.clone(),
+					approvals: vec![guardian.clone()],
+					started: <frame_system::Pallet<T>>::block_number(),
 				},
-				Err(_) => Err(Error::<T>::AccountNotRegistered)?,
+			);
+
+			Self::deposit_event(Event::RecoveryInitiated(lost, new_key, guardian));
+			Ok(())
+		}
+
+		/// Approve the recovery of `lost` as one of its guardians.
+		#[pallet::weight(10_000)]
+		pub fn approve_recovery(origin: OriginFor<T>, lost: T::AccountId) -> DispatchResult {
+			let guardian = ensure_signed(origin)?;
+			let config =
+				<RecoveryConfigs<T>>::get(&lost).ok_or(Error::<T>::RecoveryNotConfigured)?;
+			ensure!(config.guardians.contains(&guardian), Error::<T>::NotGuardian);
+			let mut recovery =
+				<ActiveRecoveries<T>>::get(&lost).ok_or(Error::<T>::RecoveryNotStarted)?;
+			ensure!(!recovery.approvals.contains(&guardian), Error::<T>::AlreadyApproved);
+
+			recovery.approvals.push(guardian.clone());
+
+			<ActiveRecoveries<T>>::insert(&lost, recovery);
+
+			Self::deposit_event(Event::RecoveryApproved(lost, guardian));
+			Ok(())
+		}
+
+		/// Cancel the recovery of the signer's account, proving the current key is not lost.
+		#[pallet::weight(10_000)]
+		pub fn cancel_recovery(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(<ActiveRecoveries<T>>::contains_key(&who), Error::<T>::RecoveryNotStarted);
+
+			<ActiveRecoveries<T>>::remove(&who);
+
+			Self::deposit_event(Event::RecoveryCancelled(who));
+			Ok(())
+		}
+
+		/// Move `lost` to the new key once enough guardians approved and the delay has passed.
+		/// Anyone can complete a recovery.
+		#[pallet::weight(Pallet::<T>::recovery_weight(
+			T::MaxLinkedKeys::get(),
+			T::OnAccountRebound::max_rebound_weight()
+		))]
+		pub fn complete_recovery(
+			origin: OriginFor<T>,
+			lost: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			let _ = ensure_signed(origin)?;
+			let config =
+				<RecoveryConfigs<T>>::get(&lost).ok_or(Error::<T>::RecoveryNotConfigured)?;
+			let recovery =
+				<ActiveRecoveries<T>>::get(&lost).ok_or(Error::<T>::RecoveryNotStarted)?;
+
+			// guardians removed from the configuration in the meantime no longer count
+			let approvals =
+				recovery.approvals.iter().filter(|g| config.guardians.contains(g)).count();
+			ensure!(approvals >= config.threshold as usize, Error::<T>::NotEnoughApprovals);
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() >=
+					recovery.started.saturating_add(config.delay),
+				Error::<T>::RecoveryDelayNotPassed
+			);
+			let new_key = recovery.new_key;
+			Self::ensure_unused_key(&new_key)?;
+			let mut account = <AccountStorage<T>>::try_get(&lost)
+				.map_err(|_| Error::<T>::AccountNotRegistered)?;
+
+			account.id = new_key.clone();
+
+			<AccountStorage<T>>::remove(&lost);
+			<AccountStorage<T>>::insert(&new_key, account);
+			if let Some(role) = <AccountRole<T>>::take(&lost) {
+				<AccountRole<T>>::insert(&new_key, role);
+			}
+			if let Some(role) = <PendingRole<T>>::take(&lost) {
+				<PendingRole<T>>::insert(&new_key, role);
+			}
+			<ActiveRecoveries<T>>::remove(&lost);
+			<RecoveryConfigs<T>>::remove(&lost);
+			<RecoveryConfigs<T>>::insert(&new_key, config);
+			let keys: Vec<T::AccountId> =
+				<KeysByAccount<T>>::drain_prefix(&lost).map(|(key, _)| key).collect();
+			let linked = keys.len() as u32;
+			for key in keys {
+				<LinkedKeys<T>>::mutate(&key, |link| {
+					if let Some((primary, _)) = link {
+						*primary = new_key.clone();
+					}
+				});
+				<KeysByAccount<T>>::insert(&new_key, &key, ());
 			}
+			<LinkedKeyCnt<T>>::insert(&new_key, <LinkedKeyCnt<T>>::take(&lost));
+
+			let hooks = T::OnAccountRebound::on_account_rebound(&lost, &new_key);
+
+			Self::deposit_event(Event::AccountRecovered(lost, new_key));
+			Ok(Some(Self::recovery_weight(linked, hooks)).into())
+		}
+
+		/// Offer to link `key` to the signer's account with the given permission. The link takes
+		/// effect once `key` accepts it.
+		#[pallet::weight(10_000)]
+		pub fn link_key(
+			origin: OriginFor<T>,
+			key: T::AccountId,
+			permission: KeyPermission,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_can_link(&who, &key)?;
+
+			<PendingLinks<T>>::insert(&who, &key, permission);
+
+			Self::deposit_event(Event::KeyLinkRequested(who, key, permission));
+			Ok(())
+		}
+
+		/// Accept the link offered by `primary`, after which the signer acts on its behalf.
+		#[pallet::weight(10_000)]
+		pub fn accept_link(origin: OriginFor<T>, primary: T::AccountId) -> DispatchResult {
+			let key = ensure_signed(origin)?;
+			let permission =
+				<PendingLinks<T>>::get(&primary, &key).ok_or(Error::<T>::NoPendingLink)?;
+			Self::ensure_can_link(&primary, &key)?;
+
+			<PendingLinks<T>>::remove(&primary, &key);
+			<LinkedKeys<T>>::insert(&key, (primary.clone(), permission));
+			<KeysByAccount<T>>::insert(&primary, &key, ());
+			<LinkedKeyCnt<T>>::mutate(&primary, |cnt| *cnt += 1);
+
+			Self::deposit_event(Event::KeyLinked(primary, key, permission));
+			Ok(())
+		}
+
+		/// Change what a secondary key linked to the signer's account may do.
+		#[pallet::weight(10_000)]
+		pub fn set_key_permission(
+			origin: OriginFor<T>,
+			key: T::AccountId,
+			permission: KeyPermission,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let (primary, _) = <LinkedKeys<T>>::get(&key).ok_or(Error::<T>::KeyNotLinked)?;
+			ensure!(primary == who, Error::<T>::KeyNotLinked);
+
+			<LinkedKeys<T>>::insert(&key, (who.clone(), permission));
+
+			Self::deposit_event(Event::KeyPermissionChanged(who, key, permission));
+			Ok(())
+		}
+
+		/// Unlink a secondary key. Can be called by the primary account or by the key itself.
+		#[pallet::weight(10_000)]
+		pub fn unlink_key(origin: OriginFor<T>, key: T::AccountId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let (primary, _) = <LinkedKeys<T>>::get(&key).ok_or(Error::<T>::KeyNotLinked)?;
+			ensure!(who == primary || who == key, Error::<T>::KeyNotLinked);
+
+			<LinkedKeys<T>>::remove(&key);
+			<KeysByAccount<T>>::remove(&primary, &key);
+			<LinkedKeyCnt<T>>::mutate(&primary, |cnt| *cnt = cnt.saturating_sub(1));
+
+			Self::deposit_event(Event::KeyUnlinked(primary, key));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Weight of `erase` for an account with `keys` linked keys, plus what the hooks took.
+		fn erase_weight(keys: u32, hooks: Weight) -> Weight {
+			let keys = keys as Weight;
+			10_000 + T::DbWeight::get().reads_writes(1 + keys, 7 + 2 * keys) + hooks
+		}
+
+		/// Weight of `complete_recovery` for an account with `keys` linked keys, plus what the
+		/// hooks took.
+		fn recovery_weight(keys: u32, hooks: Weight) -> Weight {
+			let keys = keys as Weight;
+			10_000 + T::DbWeight::get().reads_writes(9 + 2 * keys, 11 + 3 * keys) + hooks
+		}
+
+		fn ensure_unused_key(key: &T::AccountId) -> DispatchResult {
+			ensure!(!<AccountStorage<T>>::contains_key(key), Error::<T>::NewKeyInUse);
+			ensure!(!<ErasedAccounts<T>>::contains_key(key), Error::<T>::NewKeyInUse);
+			ensure!(!<LinkedKeys<T>>::contains_key(key), Error::<T>::NewKeyInUse);
+			Ok(())
+		}
+
+		fn ensure_can_link(primary: &T::AccountId, key: &T::AccountId) -> DispatchResult {
+			let account = <AccountStorage<T>>::try_get(primary)
+				.map_err(|_| Error::<T>::AccountNotRegistered)?;
+			ensure!(account.status == Status::Active, Error::<T>::AccountNotActive);
+			Self::ensure_unused_key(key)?;
+			ensure!(
+				<LinkedKeyCnt<T>>::get(primary) < T::MaxLinkedKeys::get(),
+				Error::<T>::TooManyLinkedKeys
+			);
+			Ok(())
+		}
+	}
+
+	impl<T: Config> AccountResolver<T::AccountId> for Pallet<T> {
+		fn linked_to(key: &T::AccountId) -> Option<(T::AccountId, KeyPermission)> {
+			<LinkedKeys<T>>::get(key)
+		}
+	}
+
+	impl<T: Config> AccountRegistry<T::AccountId> for Pallet<T> {
+		fn is_erased(who: &T::AccountId) -> bool {
+			<ErasedAccounts<T>>::contains_key(who)
 		}
 	}
 }
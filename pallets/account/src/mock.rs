@@ -1,11 +1,15 @@
-use crate as pallet_template;
+//! mock setup for testing pallet-account functionalities
+
+use crate as pallet_account;
 use frame_support::parameter_types;
 use frame_system as system;
+use pallet_utils::{traits::SysManAuthority, Role};
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
 };
+use std::cell::RefCell;
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -18,13 +22,16 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
-		TemplateModule: pallet_template::{Pallet, Call, Storage, Event<T>},
+		AccountModule: pallet_account::{Pallet, Call, Storage, Event<T>},
 	}
 );
 
 parameter_types! {
 	pub const BlockHashCount: u64 = 250;
 	pub const SS58Prefix: u8 = 42;
+	pub const MinRecoveryDelay: u64 = 5;
+	pub const MaxGuardians: u32 = 3;
+	pub const MaxLinkedKeys: u32 = 2;
 }
 
 impl system::Config for Test {
@@ -53,11 +60,52 @@ impl system::Config for Test {
 	type OnSetCode = ();
 }
 
-impl pallet_template::Config for Test {
+impl pallet_account::Config for Test {
 	type Event = Event;
+	type SysManAuthority = MockSysMan;
+	type OnAccountErased = ();
+	type OnAccountStatusChange = ();
+	type OnAccountRebound = ();
+	type MinRecoveryDelay = MinRecoveryDelay;
+	type MaxGuardians = MaxGuardians;
+	type MaxLinkedKeys = MaxLinkedKeys;
+}
+
+thread_local! {
+	static APPROVED_ORGS: RefCell<Vec<u64>> = RefCell::new(Vec::new());
+}
+
+/// Accounts below 10 are system managers, organizations have to be approved with
+/// `approve_org`.
+pub struct MockSysMan;
+
+impl SysManAuthority<u64> for MockSysMan {
+	fn is_authority(who: &u64) -> bool {
+		*who < 10
+	}
+
+	fn has_role(who: &u64, role: &Role) -> bool {
+		match role {
+			Role::SysMan => Self::is_authority(who),
+			Role::Organization => APPROVED_ORGS.with(|orgs| orgs.borrow().contains(who)),
+			Role::User => true,
+		}
+	}
+}
+
+pub fn approve_org(who: u64) {
+	APPROVED_ORGS.with(|orgs| orgs.borrow_mut().push(who));
+}
+
+pub fn revoke_org(who: u64) {
+	APPROVED_ORGS.with(|orgs| orgs.borrow_mut().retain(|org| *org != who));
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	APPROVED_ORGS.with(|orgs| orgs.borrow_mut().clear());
+	let t = system::GenesisConfig::default().build_storage::<Test>().unwrap().into();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
 }
@@ -1,20 +1,302 @@
+//! pallet-account functionalities test
 use crate::{mock::*, Error};
 use frame_support::{assert_noop, assert_ok};
+use pallet_utils::{traits::AccountResolver, KeyPermission, Role};
+
+fn str2vec(s: &str) -> Vec<u8> {
+	s.as_bytes().to_vec()
+}
 
 #[test]
-fn it_works_for_default_value() {
+fn update_should_persist_role_and_metadata() {
 	new_test_ext().execute_with(|| {
-		// Dispatch a signed extrinsic.
-		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
-		// Read pallet storage and assert an expected result.
-		assert_eq!(TemplateModule::something(), Some(42));
+		assert_noop!(
+			AccountModule::update(Origin::signed(20), Role::User, str2vec("new")),
+			Error::<Test>::AccountNotRegistered
+		);
+
+		assert_ok!(AccountModule::register(Origin::signed(20), Role::User, str2vec("old")));
+
+		assert_noop!(
+			AccountModule::register(Origin::signed(20), Role::User, str2vec("old")),
+			Error::<Test>::AlreadyRegistered
+		);
+
+		assert_ok!(AccountModule::update(Origin::signed(20), Role::User, str2vec("new")));
+
+		System::assert_last_event(Event::AccountModule(crate::Event::AccountUpdated(
+			20,
+			Role::User,
+			Role::User,
+			str2vec("old"),
+			str2vec("new"),
+		)));
+		assert_eq!(AccountModule::account_role(20), Some(Role::User));
+
+		// the stored metadata is the one reported as old by the next update
+		assert_ok!(AccountModule::update(Origin::signed(20), Role::User, str2vec("newer")));
+
+		System::assert_last_event(Event::AccountModule(crate::Event::AccountUpdated(
+			20,
+			Role::User,
+			Role::User,
+			str2vec("new"),
+			str2vec("newer"),
+		)));
+
+		// privileged roles are never self-assigned
+		assert_noop!(
+			AccountModule::update(Origin::signed(20), Role::SysMan, str2vec("newer")),
+			Error::<Test>::RoleNotAllowed
+		);
 	});
 }
 
 #[test]
-fn correct_error_for_none_value() {
+fn privileged_roles_should_wait_for_sys_man_approval() {
 	new_test_ext().execute_with(|| {
-		// Ensure the expected error is thrown when no value is present.
-		assert_noop!(TemplateModule::cause_error(Origin::signed(1)), Error::<Test>::NoneValue);
+		// approved system managers get their role right away
+		assert_ok!(AccountModule::register(Origin::signed(1), Role::SysMan, vec![]));
+
+		assert_eq!(AccountModule::account_role(1), Some(Role::SysMan));
+		assert_eq!(AccountModule::pending_role(1), None);
+
+		// organizations not approved yet start out as users
+		assert_ok!(AccountModule::register(Origin::signed(20), Role::Organization, vec![]));
+
+		assert_eq!(AccountModule::account_role(20), Some(Role::User));
+		assert_eq!(AccountModule::pending_role(20), Some(Role::Organization));
+
+		assert_noop!(
+			AccountModule::confirm_role(Origin::signed(30), 20),
+			Error::<Test>::NotSysManAuthority
+		);
+		assert_noop!(
+			AccountModule::confirm_role(Origin::signed(1), 20),
+			Error::<Test>::RoleNotApproved
+		);
+
+		approve_org(20);
+
+		assert_ok!(AccountModule::confirm_role(Origin::signed(1), 20));
+
+		assert_eq!(AccountModule::account_role(20), Some(Role::Organization));
+		assert_eq!(AccountModule::pending_role(20), None);
+		assert_noop!(
+			AccountModule::confirm_role(Origin::signed(1), 20),
+			Error::<Test>::NoPendingRole
+		);
+
+		// the role goes once sys-man no longer approves it
+		assert_noop!(
+			AccountModule::demote_role(Origin::signed(30), 20),
+			Error::<Test>::RoleStillApproved
+		);
+
+		revoke_org(20);
+
+		assert_ok!(AccountModule::demote_role(Origin::signed(30), 20));
+
+		System::assert_last_event(Event::AccountModule(crate::Event::RoleWithdrawn(
+			20,
+			Role::Organization,
+		)));
+		assert_eq!(AccountModule::account_role(20), Some(Role::User));
+		assert_noop!(
+			AccountModule::demote_role(Origin::signed(30), 20),
+			Error::<Test>::RoleStillApproved
+		);
+
+		// a rejected request leaves the account a user
+		assert_ok!(AccountModule::register(Origin::signed(21), Role::Organization, vec![]));
+		assert_ok!(AccountModule::reject_role(Origin::signed(1), 21));
+
+		assert_eq!(AccountModule::account_role(21), Some(Role::User));
+		assert_eq!(AccountModule::pending_role(21), None);
+	});
+}
+
+#[test]
+fn deactivated_accounts_should_reactivate_and_erased_accounts_stay_erased() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AccountModule::register(Origin::signed(20), Role::User, str2vec("me")));
+
+		assert_noop!(
+			AccountModule::reactivate(Origin::signed(20)),
+			Error::<Test>::AccountNotDeactivated
+		);
+
+		assert_ok!(AccountModule::deactivate(Origin::signed(20)));
+
+		System::assert_last_event(Event::AccountModule(crate::Event::AccountDeactivated(20)));
+		assert_noop!(
+			AccountModule::update(Origin::signed(20), Role::User, str2vec("new")),
+			Error::<Test>::AccountNotActive
+		);
+		assert_noop!(
+			AccountModule::deactivate(Origin::signed(20)),
+			Error::<Test>::AccountNotActive
+		);
+
+		assert_ok!(AccountModule::reactivate(Origin::signed(20)));
+
+		System::assert_last_event(Event::AccountModule(crate::Event::AccountReactivated(20)));
+		assert_ok!(AccountModule::update(Origin::signed(20), Role::User, str2vec("new")));
+
+		assert_ok!(AccountModule::erase(Origin::signed(20)));
+
+		assert_eq!(AccountModule::account_role(20), None);
+		assert!(AccountModule::account_storage(20).is_none());
+		assert!(AccountModule::erased_account(20).is_some());
+		assert_noop!(
+			AccountModule::register(Origin::signed(20), Role::User, str2vec("me")),
+			Error::<Test>::AccountErased
+		);
+		assert_noop!(AccountModule::erase(Origin::signed(20)), Error::<Test>::AccountNotRegistered);
+	});
+}
+
+#[test]
+fn recovery_should_wait_for_the_delay_and_be_cancellable() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AccountModule::register(Origin::signed(20), Role::User, str2vec("me")));
+
+		assert_noop!(
+			AccountModule::set_guardians(Origin::signed(20), vec![31, 32, 33], 2, 4),
+			Error::<Test>::RecoveryDelayTooShort
+		);
+		assert_noop!(
+			AccountModule::set_guardians(Origin::signed(20), vec![31, 32, 33, 34], 2, 5),
+			Error::<Test>::TooManyGuardians
+		);
+		assert_noop!(
+			AccountModule::set_guardians(Origin::signed(20), vec![31, 31], 1, 5),
+			Error::<Test>::InvalidGuardians
+		);
+
+		assert_ok!(AccountModule::set_guardians(Origin::signed(20), vec![31, 32, 33], 2, 5));
+
+		assert_noop!(
+			AccountModule::initiate_recovery(Origin::signed(40), 20, 40),
+			Error::<Test>::NotGuardian
+		);
+
+		assert_ok!(AccountModule::initiate_recovery(Origin::signed(31), 20, 40));
+
+		assert_noop!(
+			AccountModule::complete_recovery(Origin::signed(40), 20),
+			Error::<Test>::NotEnoughApprovals
+		);
+
+		assert_ok!(AccountModule::approve_recovery(Origin::signed(32), 20));
+
+		assert_noop!(
+			AccountModule::complete_recovery(Origin::signed(40), 20),
+			Error::<Test>::RecoveryDelayNotPassed
+		);
+
+		// the owner still holds the key and stops the recovery
+		assert_ok!(AccountModule::cancel_recovery(Origin::signed(20)));
+
+		System::assert_last_event(Event::AccountModule(crate::Event::RecoveryCancelled(20)));
+		System::set_block_number(6);
+		assert_noop!(
+			AccountModule::complete_recovery(Origin::signed(40), 20),
+			Error::<Test>::RecoveryNotStarted
+		);
+
+		assert_ok!(AccountModule::initiate_recovery(Origin::signed(31), 20, 40));
+		assert_ok!(AccountModule::approve_recovery(Origin::signed(33), 20));
+		System::set_block_number(10);
+
+		assert_noop!(
+			AccountModule::complete_recovery(Origin::signed(40), 20),
+			Error::<Test>::RecoveryDelayNotPassed
+		);
+
+		System::set_block_number(11);
+
+		assert_ok!(AccountModule::complete_recovery(Origin::signed(40), 20));
+
+		System::assert_last_event(Event::AccountModule(crate::Event::AccountRecovered(20, 40)));
+		assert_eq!(AccountModule::account_role(20), None);
+		assert_eq!(AccountModule::account_role(40), Some(Role::User));
+		assert!(AccountModule::recovery_config(20).is_none());
+		assert!(AccountModule::recovery_config(40).is_some());
+		assert!(AccountModule::active_recovery(20).is_none());
+	});
+}
+
+#[test]
+fn linked_keys_should_resolve_to_their_primary_account() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AccountModule::register(Origin::signed(20), Role::User, str2vec("me")));
+		assert_ok!(AccountModule::register(Origin::signed(30), Role::User, str2vec("other")));
+
+		assert_noop!(
+			AccountModule::link_key(Origin::signed(20), 30, KeyPermission::ReadOnly),
+			Error::<Test>::NewKeyInUse
+		);
+
+		assert_ok!(AccountModule::link_key(Origin::signed(20), 21, KeyPermission::ReadOnly));
+		// another account's offer doesn't replace the first one
+		assert_ok!(AccountModule::link_key(Origin::signed(30), 21, KeyPermission::SubmitItems));
+
+		// offered links have no effect until the key accepts them
+		assert_eq!(AccountModule::resolve(&21), (21, KeyPermission::SubmitItems));
+		assert_noop!(
+			AccountModule::accept_link(Origin::signed(21), 40),
+			Error::<Test>::NoPendingLink
+		);
+
+		assert_ok!(AccountModule::accept_link(Origin::signed(21), 20));
+
+		System::assert_last_event(Event::AccountModule(crate::Event::KeyLinked(
+			20,
+			21,
+			KeyPermission::ReadOnly,
+		)));
+		assert_eq!(AccountModule::resolve(&21), (20, KeyPermission::ReadOnly));
+		assert_eq!(AccountModule::resolve(&20), (20, KeyPermission::SubmitItems));
+		assert_noop!(
+			AccountModule::accept_link(Origin::signed(21), 30),
+			Error::<Test>::NewKeyInUse
+		);
+		assert_noop!(
+			AccountModule::register(Origin::signed(21), Role::User, str2vec("key")),
+			Error::<Test>::NotPrimaryKey
+		);
+
+		assert_ok!(AccountModule::set_key_permission(
+			Origin::signed(20),
+			21,
+			KeyPermission::SubmitItems
+		));
+
+		assert_eq!(AccountModule::resolve(&21), (20, KeyPermission::SubmitItems));
+		assert_noop!(
+			AccountModule::set_key_permission(Origin::signed(30), 21, KeyPermission::ReadOnly),
+			Error::<Test>::KeyNotLinked
+		);
+
+		assert_ok!(AccountModule::link_key(Origin::signed(20), 22, KeyPermission::ReadOnly));
+		assert_ok!(AccountModule::accept_link(Origin::signed(22), 20));
+
+		assert_eq!(AccountModule::linked_key_cnt(20), 2);
+		assert_noop!(
+			AccountModule::link_key(Origin::signed(20), 23, KeyPermission::ReadOnly),
+			Error::<Test>::TooManyLinkedKeys
+		);
+
+		// either side may unlink
+		assert_ok!(AccountModule::unlink_key(Origin::signed(21), 21));
+		assert_ok!(AccountModule::unlink_key(Origin::signed(20), 22));
+
+		assert_eq!(AccountModule::resolve(&21), (21, KeyPermission::SubmitItems));
+		assert_eq!(AccountModule::linked_key_cnt(20), 0);
+		assert_noop!(
+			AccountModule::unlink_key(Origin::signed(20), 22),
+			Error::<Test>::KeyNotLinked
+		);
 	});
 }
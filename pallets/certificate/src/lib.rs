@@ -18,7 +18,10 @@ mod benchmarking;
 pub mod pallet {
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
-	use pallet_utils::{Role, Status, TypeID, String};
+	use pallet_utils::{
+		traits::{AccountResolver, OnAccountRebound, OrgMembership},
+		KeyPermission, Role, Status, String, TypeID,
+	};
 	use scale_info::TypeInfo;
 	use frame_support::inherent::Vec;
 
@@ -36,6 +39,13 @@ pub mod pallet {
 	pub trait Config: frame_system::Config {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// Resolves secondary keys to the account they act for.
+		type AccountResolver: AccountResolver<Self::AccountId>;
+		/// Follows orgs through the key rotations of sys-man.
+		type OrgMembership: OrgMembership<Self::AccountId>;
+		/// Maximum number of certificates an org can hold at once.
+		#[pallet::constant]
+		type MaxCertificatesPerOrg: Get<u32>;
 	}
 
 	#[pallet::pallet]
@@ -57,6 +67,17 @@ pub mod pallet {
 	// https://docs.substrate.io/v3/runtime/storage#declaring-storage-items
 	pub type CertificateById<T> = StorageMap<_, Twox64Concat, TypeID, Certificate<T>, OptionQuery>;
 
+	#[pallet::storage]
+	/// Certificates indexed by the org that created them.
+	pub type CertificatesByOrg<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, TypeID, (), OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn certificate_cnt)]
+	/// Number of certificates held by an org.
+	pub type CertificateCntByOrg<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
 	// Pallets use events to inform users when important changes are made.
 	// https://docs.substrate.io/v3/runtime/events-and-errors
 	#[pallet::event]
@@ -65,7 +86,10 @@ pub mod pallet {
 		/// Event documentation should end with an array that provides descriptive names for event
 		/// parameters. [something, who]
 		CertificateCreated(T::AccountId),
-        CertificateRevoked(T::AccountId),
+		/// An org revoked one of its certificates. [org, cid]
+		CertificateRevoked(T::AccountId, TypeID),
+		/// Certificates of a recovered org were moved to its new key. [old, new]
+		CertificatesRebound(T::AccountId, T::AccountId),
 	}
 
 	// Errors inform users that something went wrong.
@@ -75,6 +99,14 @@ pub mod pallet {
 		NoneValue,
 		/// Errors should have helpful documentation associated with them.
 		StorageOverflow,
+		/// Signing key is not allowed to act for its account
+		KeyNotPermitted,
+		/// The org holds the maximum number of certificates
+		TooManyCertificates,
+		/// No certificate with this id
+		CertificateNotFound,
+		/// Only the org that created the certificate can do this
+		NotCertificateOwner,
 	}
 
 	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
@@ -89,7 +121,11 @@ pub mod pallet {
 			// Check that the extrinsic was signed and get the signer.
 			// This function will return an error if the extrinsic is not signed.
 			// https://docs.substrate.io/v3/runtime/origins
-			let who = ensure_signed(origin)?;
+			let who = Self::acting_account(ensure_signed(origin)?)?;
+			ensure!(
+				<CertificateCntByOrg<T>>::get(&who) < T::MaxCertificatesPerOrg::get(),
+				Error::<T>::TooManyCertificates
+			);
 			let cid = <CertificateId<T>>::get();
 			// Update storage.
 			<CertificateById<T>>::insert(cid, Certificate {
@@ -98,6 +134,8 @@ pub mod pallet {
 				metadata: _meta_data,
 				scrore: 5,
 			});
+			<CertificatesByOrg<T>>::insert(&who, cid, ());
+			<CertificateCntByOrg<T>>::mutate(&who, |cnt| *cnt += 1);
             <CertificateId<T>>::mutate(|n| {
 				*n += 1;
 			});
@@ -107,14 +145,61 @@ pub mod pallet {
 			Ok(())
 		}
 
-		/// An example dispatchable that may throw a custom error.
+		/// Revoke a certificate. Must be called by the org that created it, under its current key.
 		#[pallet::weight(10_000)]
 		pub fn revoke_certificate(origin: OriginFor<T>, _cid: TypeID) -> DispatchResult {
-			let _who = ensure_signed(origin)?;
+			let _who = Self::acting_account(ensure_signed(origin)?)?;
+			let certificate =
+				<CertificateById<T>>::get(_cid).ok_or(Error::<T>::CertificateNotFound)?;
+			// the org may have rotated its key since it created the certificate
+			ensure!(
+				T::OrgMembership::current_org_key(&certificate.org) == _who,
+				Error::<T>::NotCertificateOwner
+			);
+
 			<CertificateById<T>>::remove(_cid);
-			Self::deposit_event(Event::CertificateRevoked(_who));
+			<CertificatesByOrg<T>>::remove(&certificate.org, _cid);
+			<CertificateCntByOrg<T>>::mutate(&certificate.org, |cnt| *cnt = cnt.saturating_sub(1));
+			Self::deposit_event(Event::CertificateRevoked(_who, _cid));
 			// Return a successful DispatchResultWithPostInfo
 			Ok(())
 		}
 	}
+
+	impl<T: Config> Pallet<T> {
+		/// Account a signing key acts for, provided the key is not read-only.
+		fn acting_account(key: T::AccountId) -> Result<T::AccountId, DispatchError> {
+			let (who, permission) = T::AccountResolver::resolve(&key);
+			ensure!(permission == KeyPermission::SubmitItems, Error::<T>::KeyNotPermitted);
+			Ok(who)
+		}
+
+		/// Weight of moving `certificates` certificates of a recovered org to its new key.
+		fn rebound_weight(certificates: Weight) -> Weight {
+			T::DbWeight::get().reads_writes(1 + 2 * certificates, 2 + 3 * certificates)
+		}
+	}
+
+	impl<T: Config> OnAccountRebound<T::AccountId> for Pallet<T> {
+		fn on_account_rebound(old: &T::AccountId, new: &T::AccountId) -> Weight {
+			let cids: Vec<TypeID> =
+				<CertificatesByOrg<T>>::drain_prefix(old).map(|(cid, _)| cid).collect();
+			let certificates = cids.len() as Weight;
+			for cid in cids {
+				<CertificateById<T>>::mutate(cid, |maybe_certificate| {
+					if let Some(certificate) = maybe_certificate {
+						certificate.org = new.clone();
+					}
+				});
+				<CertificatesByOrg<T>>::insert(new, cid, ());
+			}
+			<CertificateCntByOrg<T>>::insert(new, <CertificateCntByOrg<T>>::take(old));
+			Self::deposit_event(Event::CertificatesRebound(old.clone(), new.clone()));
+			Self::rebound_weight(certificates)
+		}
+
+		fn max_rebound_weight() -> Weight {
+			Self::rebound_weight(T::MaxCertificatesPerOrg::get() as Weight)
+		}
+	}
 }
@@ -1,6 +1,12 @@
-use crate as pallet_template;
+//! mock setup for testing pallet-certificate functionalities
+
+use crate as pallet_certificate;
 use frame_support::parameter_types;
 use frame_system as system;
+use pallet_utils::{
+	traits::{AccountResolver, OrgMembership},
+	KeyPermission, OrgRole,
+};
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
@@ -18,13 +24,14 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
-		TemplateModule: pallet_template::{Pallet, Call, Storage, Event<T>},
+		CertificateModule: pallet_certificate::{Pallet, Call, Storage, Event<T>},
 	}
 );
 
 parameter_types! {
 	pub const BlockHashCount: u64 = 250;
 	pub const SS58Prefix: u8 = 42;
+	pub const MaxCertificatesPerOrg: u32 = 2;
 }
 
 impl system::Config for Test {
@@ -53,11 +60,50 @@ impl system::Config for Test {
 	type OnSetCode = ();
 }
 
-impl pallet_template::Config for Test {
+impl pallet_certificate::Config for Test {
 	type Event = Event;
+	type AccountResolver = MockResolver;
+	type OrgMembership = MockOrgs;
+	type MaxCertificatesPerOrg = MaxCertificatesPerOrg;
+}
+
+/// Key 21 is a read-only key of org 20 and key 22 may submit for it.
+pub struct MockResolver;
+
+impl AccountResolver<u64> for MockResolver {
+	fn linked_to(key: &u64) -> Option<(u64, KeyPermission)> {
+		match key {
+			21 => Some((20, KeyPermission::ReadOnly)),
+			22 => Some((20, KeyPermission::SubmitItems)),
+			_ => None,
+		}
+	}
+}
+
+/// Org 40 rotated its key to 41, every other account is its own org.
+pub struct MockOrgs;
+
+impl OrgMembership<u64> for MockOrgs {
+	fn current_org_key(org: &u64) -> u64 {
+		match org {
+			40 => 41,
+			_ => *org,
+		}
+	}
+
+	fn is_active_org(org: &u64) -> bool {
+		Self::current_org_key(org) == *org
+	}
+
+	fn member_role(org: &u64, who: &u64) -> Option<OrgRole> {
+		(Self::is_active_org(org) && org == who).then(|| OrgRole::Admin)
+	}
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	let t = system::GenesisConfig::default().build_storage::<Test>().unwrap().into();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
 }
@@ -1,20 +1,117 @@
-use crate::{mock::*, Error};
+//! pallet-certificate functionalities test
+use crate::{mock::*, CertificatesByOrg, Error};
 use frame_support::{assert_noop, assert_ok};
+use pallet_utils::traits::OnAccountRebound;
+
+fn str2vec(s: &str) -> Vec<u8> {
+	s.as_bytes().to_vec()
+}
+
+#[test]
+fn certificates_should_be_bounded_per_org() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CertificateModule::create_certificate(Origin::signed(20), str2vec("first")));
+
+		System::assert_last_event(Event::CertificateModule(crate::Event::CertificateCreated(20)));
+
+		// linked keys create certificates for their org
+		assert_ok!(CertificateModule::create_certificate(Origin::signed(22), str2vec("second")));
+
+		assert_eq!(CertificateModule::certificate_cnt(20), 2);
+		assert_eq!(CertificateModule::certificate_id(), 2);
+		assert!(CertificatesByOrg::<Test>::contains_key(20, 1));
+		assert_noop!(
+			CertificateModule::create_certificate(Origin::signed(20), str2vec("third")),
+			Error::<Test>::TooManyCertificates
+		);
+
+		assert_ok!(CertificateModule::revoke_certificate(Origin::signed(20), 0));
+
+		assert_eq!(CertificateModule::certificate_cnt(20), 1);
+		assert!(CertificateModule::certificate_by_id(0).is_none());
+		assert_ok!(CertificateModule::create_certificate(Origin::signed(20), str2vec("third")));
+	});
+}
+
+#[test]
+fn read_only_keys_should_not_manage_certificates() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			CertificateModule::create_certificate(Origin::signed(21), str2vec("first")),
+			Error::<Test>::KeyNotPermitted
+		);
+
+		assert_ok!(CertificateModule::create_certificate(Origin::signed(20), str2vec("first")));
+
+		assert_noop!(
+			CertificateModule::revoke_certificate(Origin::signed(21), 0),
+			Error::<Test>::KeyNotPermitted
+		);
+	});
+}
+
+#[test]
+fn certificates_should_follow_rotated_org_keys() {
+	new_test_ext().execute_with(|| {
+		// created by org 40 before it rotated its key to 41
+		assert_ok!(CertificateModule::create_certificate(Origin::signed(40), str2vec("first")));
+
+		assert_noop!(
+			CertificateModule::revoke_certificate(Origin::signed(40), 0),
+			Error::<Test>::NotCertificateOwner
+		);
+
+		assert_ok!(CertificateModule::revoke_certificate(Origin::signed(41), 0));
+
+		System::assert_last_event(Event::CertificateModule(crate::Event::CertificateRevoked(
+			41, 0,
+		)));
+		assert_eq!(CertificateModule::certificate_cnt(40), 0);
+	});
+}
 
 #[test]
-fn it_works_for_default_value() {
+fn only_the_owning_org_should_revoke_a_certificate() {
 	new_test_ext().execute_with(|| {
-		// Dispatch a signed extrinsic.
-		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
-		// Read pallet storage and assert an expected result.
-		assert_eq!(TemplateModule::something(), Some(42));
+		assert_ok!(CertificateModule::create_certificate(Origin::signed(20), str2vec("first")));
+
+		assert_noop!(
+			CertificateModule::revoke_certificate(Origin::signed(30), 0),
+			Error::<Test>::NotCertificateOwner
+		);
+		assert_noop!(
+			CertificateModule::revoke_certificate(Origin::signed(20), 1),
+			Error::<Test>::CertificateNotFound
+		);
+
+		// linked keys revoke for their org
+		assert_ok!(CertificateModule::revoke_certificate(Origin::signed(22), 0));
+
+		System::assert_last_event(Event::CertificateModule(crate::Event::CertificateRevoked(
+			20, 0,
+		)));
+		assert_noop!(
+			CertificateModule::revoke_certificate(Origin::signed(20), 0),
+			Error::<Test>::CertificateNotFound
+		);
 	});
 }
 
 #[test]
-fn correct_error_for_none_value() {
+fn certificates_should_follow_a_recovered_org() {
 	new_test_ext().execute_with(|| {
-		// Ensure the expected error is thrown when no value is present.
-		assert_noop!(TemplateModule::cause_error(Origin::signed(1)), Error::<Test>::NoneValue);
+		assert_ok!(CertificateModule::create_certificate(Origin::signed(20), str2vec("first")));
+		assert_ok!(CertificateModule::create_certificate(Origin::signed(20), str2vec("second")));
+
+		CertificateModule::on_account_rebound(&20, &40);
+
+		System::assert_last_event(Event::CertificateModule(crate::Event::CertificatesRebound(
+			20, 40,
+		)));
+		assert_eq!(CertificateModule::certificate_cnt(20), 0);
+		assert_eq!(CertificateModule::certificate_cnt(40), 2);
+		assert!(!CertificatesByOrg::<Test>::contains_key(20, 0));
+		assert!(CertificatesByOrg::<Test>::contains_key(40, 0));
+		assert!(CertificatesByOrg::<Test>::contains_key(40, 1));
 	});
 }
@@ -11,25 +11,151 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+pub mod migrations;
+
 #[frame_support::pallet]
 pub mod pallet {
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
-	use pallet_utils::{String, TypeID, UnixEpoch, WhoAndWhen};
+	use pallet_utils::{
+		traits::{
+			AccountRegistry, AccountResolver, OnAccountErased, OnAccountRebound,
+			OnAccountStatusChange, OrgMembership,
+		},
+		KeyPermission, OrgRole, String, TypeID, UnixEpoch, WhoAndWhen,
+	};
 	use scale_info::TypeInfo;
 	use frame_support::inherent::Vec;
+	use sp_runtime::SaturatedConversion;
+
+	/// Length in milliseconds of the buckets of the expiry index.
+	const EXPIRY_BUCKET: UnixEpoch = 24 * 60 * 60 * 1000;
+
+	/// The current storage version.
+	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+	/// Category of a CV item.
+	#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub enum ItemType {
+		Education,
+		Employment,
+		Skill,
+		Language,
+		Publication,
+		Award,
+	}
+
+	/// Structured fields of a CV item, one field set per category. Dates are unix epochs.
+	#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub enum ItemFields {
+		Education {
+			degree: String,
+			institution: String,
+			field_of_study: String,
+			start: Option<UnixEpoch>,
+			end: Option<UnixEpoch>,
+		},
+		Employment {
+			job_title: String,
+			employer: String,
+			start: Option<UnixEpoch>,
+			end: Option<UnixEpoch>,
+		},
+		Skill {
+			name: String,
+			/// From 1 (basic) to `MAX_LEVEL` (expert).
+			level: u8,
+		},
+		Language {
+			language: String,
+			/// From 1 (basic) to `MAX_LEVEL` (native).
+			proficiency: u8,
+		},
+		Publication {
+			title: String,
+			publisher: String,
+			published: Option<UnixEpoch>,
+			url: String,
+		},
+		Award {
+			title: String,
+			awarded_by: String,
+			awarded: Option<UnixEpoch>,
+		},
+	}
+
+	impl ItemFields {
+		/// Highest skill or language level.
+		pub const MAX_LEVEL: u8 = 5;
+
+		pub fn item_type(&self) -> ItemType {
+			match self {
+				Self::Education { .. } => ItemType::Education,
+				Self::Employment { .. } => ItemType::Employment,
+				Self::Skill { .. } => ItemType::Skill,
+				Self::Language { .. } => ItemType::Language,
+				Self::Publication { .. } => ItemType::Publication,
+				Self::Award { .. } => ItemType::Award,
+			}
+		}
+
+		/// Check that required fields are set, text fields are at most `max_len` bytes, periods
+		/// don't end before they start and levels are in range.
+		pub fn validate<T: Config>(&self, max_len: usize) -> DispatchResult {
+			let (required, optional, period, level): (&[&String], &[&String], _, _) = match self {
+				Self::Education { degree, institution, field_of_study, start, end } =>
+					(&[degree, institution], &[field_of_study], (*start, *end), None),
+				Self::Employment { job_title, employer, start, end } =>
+					(&[job_title, employer], &[], (*start, *end), None),
+				Self::Skill { name, level } => (&[name], &[], (None, None), Some(*level)),
+				Self::Language { language, proficiency } =>
+					(&[language], &[], (None, None), Some(*proficiency)),
+				Self::Publication { title, publisher, url, .. } =>
+					(&[title], &[publisher, url], (None, None), None),
+				Self::Award { title, awarded_by, .. } =>
+					(&[title, awarded_by], &[], (None, None), None),
+			};
+
+			ensure!(required.iter().all(|f| !f.is_empty()), Error::<T>::MissingItemField);
+			ensure!(
+				required.iter().chain(optional.iter()).all(|f| f.len() <= max_len),
+				Error::<T>::ItemFieldTooLong
+			);
+			if let (Some(start), Some(end)) = period {
+				ensure!(start <= end, Error::<T>::InvalidItemPeriod);
+			}
+			if let Some(level) = level {
+				ensure!((1..=Self::MAX_LEVEL).contains(&level), Error::<T>::InvalidItemLevel);
+			}
+			Ok(())
+		}
+	}
 
 	#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
 	#[scale_info(bounds(), skip_type_params(T))]
 	pub struct Item<T: Config> {
-		item_id: TypeID,
-		user_id: T::AccountId,
-		created: WhoAndWhen<T>,
-		org_date: Option<UnixEpoch>,
-		exp_date: Option<UnixEpoch>,
-		certificate_id: Option<TypeID>,
-		score: u32,
-		metadata: String,
+		pub item_id: TypeID,
+		/// Account the item is about.
+		pub user_id: T::AccountId,
+		/// Account that issued the item.
+		pub created: WhoAndWhen<T>,
+		pub org_date: Option<UnixEpoch>,
+		/// Timestamp in milliseconds after which the item is expired.
+		pub exp_date: Option<UnixEpoch>,
+		pub certificate_id: Option<TypeID>,
+		pub score: u32,
+		pub metadata: String,
+		/// Set by the subject to keep the item out of its public CV.
+		pub hidden: bool,
+		/// Structured fields, `None` for items created before they existed or once scrubbed
+		/// from an erased account.
+		pub fields: Option<ItemFields>,
+		/// Set by the expiry sweep once `exp_date` has passed.
+		pub expired: bool,
+		/// Starts at 1 and goes up with every update.
+		pub version: u32,
+		/// Who made this version and when, `None` for the version created with the item.
+		pub updated: Option<WhoAndWhen<T>>,
 	}
 
 	impl<T: Config> Item<T> {
@@ -42,6 +168,7 @@ pub mod pallet {
 			certificate_id: Option<TypeID>,
 			score: u32,
 			metadata: String,
+			fields: ItemFields,
 		) -> Self {
 			Item {
 				item_id: id,
@@ -52,17 +179,30 @@ pub mod pallet {
 				certificate_id,
 				score,
 				metadata,
+				hidden: false,
+				fields: Some(fields),
+				expired: false,
+				version: 1,
+				updated: None,
 			}
 		}
 
-		// pub fn ensure_owner(&self, account: &T::AccountId) -> DispatchResult {
-		// 	ensure!(self.is_owner(account), Error::<T>::NotAPostOwner);
-		// 	Ok(())
-		// }
+		pub fn is_subject(&self, account: &T::AccountId) -> bool {
+			self.user_id == *account
+		}
 
-		// pub fn is_owner(&self, account: &T::AccountId) -> bool {
-		// 	self.owner == *account
-		// }
+		pub fn is_issuer(&self, account: &T::AccountId) -> bool {
+			self.created.account == *account
+		}
+
+		/// Whether the item is past its expiry date at `now`, even if not swept yet.
+		pub fn is_expired(&self, now: UnixEpoch) -> bool {
+			self.expired || self.exp_date.map_or(false, |exp_date| exp_date <= now)
+		}
+
+		pub fn item_type(&self) -> Option<ItemType> {
+			self.fields.as_ref().map(ItemFields::item_type)
+		}
 	}
 
 	#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
@@ -77,15 +217,55 @@ pub mod pallet {
         }
 	}
 
+	/// Verification of an item requested from an organization.
+	#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	#[scale_info(bounds(), skip_type_params(T))]
+	pub struct Verification<T: Config> {
+		pub org: T::AccountId,
+		pub requested: WhoAndWhen<T>,
+		/// Org member that set the status and when, `None` while the request is pending.
+		pub verified: Option<WhoAndWhen<T>>,
+		pub reason: String,
+	}
+
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
 	pub trait Config: frame_system::Config + pallet_utils::Config {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// Resolves secondary keys to the account they act for.
+		type AccountResolver: AccountResolver<Self::AccountId>;
+		/// Tells which accounts were erased by their owner.
+		type AccountRegistry: AccountRegistry<Self::AccountId>;
+		/// Source of the organization staff allowed to verify items.
+		type OrgMembership: OrgMembership<Self::AccountId>;
+		/// Maximum length in bytes of a text field of an item.
+		#[pallet::constant]
+		type MaxItemFieldLength: Get<u32>;
+		/// Maximum length in bytes of the metadata of an item.
+		#[pallet::constant]
+		type MaxItemMetadataLength: Get<u32>;
+		/// Maximum number of items the expiry sweep handles in a block.
+		#[pallet::constant]
+		type MaxExpiriesPerBlock: Get<u32>;
+		/// Maximum number of items in the CV of an account.
+		#[pallet::constant]
+		type MaxItemsPerSubject: Get<u32>;
+		/// Maximum number of items an account can have issued at once.
+		#[pallet::constant]
+		type MaxItemsPerIssuer: Get<u32>;
+		/// Maximum number of items a single issuer can have in the CV of an account, so no
+		/// stranger can fill it up on its own.
+		#[pallet::constant]
+		type MaxItemsPerIssuerAndSubject: Get<u32>;
+		/// Maximum number of prior versions kept for an item, older ones are pruned.
+		#[pallet::constant]
+		type MaxItemVersions: Get<u32>;
 	}
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	// The pallet's runtime storage items.
@@ -105,10 +285,47 @@ pub mod pallet {
 	pub type ItemStatusByItemId<T> = StorageMap<_, Twox64Concat, TypeID, Status, ValueQuery>;
 
 	#[pallet::storage]
-	#[pallet::getter(fn items_by_accountid)]
-	pub type ItemsByAccountId<T: Config> =
+	#[pallet::getter(fn verification_by_item_id)]
+	/// Latest verification requested for an item.
+	pub type VerificationByItemId<T: Config> =
+		StorageMap<_, Twox64Concat, TypeID, Verification<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn item_version)]
+	/// Prior versions of updated items, by item and version. Only the latest
+	/// `MaxItemVersions` are kept.
+	pub type ItemVersions<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, TypeID, Twox64Concat, u32, Item<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn items_expiring_in)]
+	/// Items with an expiry date, by the day they expire on.
+	pub type ExpiringItems<T: Config> =
+		StorageMap<_, Twox64Concat, UnixEpoch, Vec<TypeID>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_expiry_bucket)]
+	/// First day of `ExpiringItems` the sweep has not finished yet.
+	pub type NextExpiryBucket<T: Config> = StorageValue<_, UnixEpoch, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn items_by_subject)]
+	/// Items about an account, making up its CV.
+	pub type ItemsBySubject<T: Config> =
 		StorageMap<_, Twox64Concat, T::AccountId, Vec<TypeID>, ValueQuery>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn items_by_issuer)]
+	/// Items issued by an account.
+	pub type ItemsByIssuer<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, Vec<TypeID>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn is_subject_deactivated)]
+	/// Subjects whose account is deactivated, their CV is hidden until they reactivate it.
+	pub type DeactivatedSubjects<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, (), OptionQuery>;
+
 	// Pallets use events to inform users when important changes are made.
 	// https://docs.substrate.io/v3/runtime/events-and-errors
 	#[pallet::event]
@@ -116,7 +333,24 @@ pub mod pallet {
 	pub enum Event<T: Config> {
 		RevokeSucceed(TypeID),
 		CreateSucceed(TypeID),
-		SetStatusSucceed(TypeID),
+		/// The subject of an item asked an organization to verify it. [item_id, org]
+		VerificationRequested(TypeID, T::AccountId),
+		/// An organization allowed or denied an item. [item_id, org, verifier, status]
+		ItemVerified(TypeID, T::AccountId, T::AccountId, Status),
+		/// The subject of an item hid it from or showed it in its CV. [item_id, hidden]
+		ItemVisibilityChanged(TypeID, bool),
+		/// Personal data of the items of an erased account was scrubbed. [who]
+		AccountItemsErased(T::AccountId),
+		/// The issuer of an item published a new version of it. [item_id, version]
+		ItemUpdated(TypeID, u32),
+		/// An update changed verified content, putting the item back to `Pending`. [item_id]
+		EndorsementReset(TypeID),
+		/// An item passed its expiry date. [item_id]
+		ItemExpired(TypeID),
+		/// Items of a recovered account were moved to its new key. [old, new]
+		AccountItemsRebound(T::AccountId, T::AccountId),
+		/// The subject of an item removed it from its CV. [item_id]
+		ItemDismissed(TypeID),
 	}
 
 	// Errors inform users that something went wrong.
@@ -126,6 +360,60 @@ pub mod pallet {
 		ItemNotFound,
 		/// Errors should have helpful documentation associated with them.
 		StorageOverflow,
+		/// Signing key is not allowed to submit items for its account
+		KeyNotPermitted,
+		/// Only the account the item is about can do this
+		NotItemSubject,
+		/// Only the account that issued the item can do this
+		NotItemIssuer,
+		/// No verification of the item is pending
+		NoPendingVerification,
+		/// Caller can not verify items for the organization
+		NotOrgVerifier,
+		/// Verification can only allow or deny an item
+		InvalidStatus,
+		/// A required field of the item is empty
+		MissingItemField,
+		/// A text field of the item is too long
+		ItemFieldTooLong,
+		/// The metadata of the item is too long
+		ItemMetadataTooLong,
+		/// The reason given with a verification is too long
+		ReasonTooLong,
+		/// The item ends before it starts
+		InvalidItemPeriod,
+		/// Skill or language level is out of range
+		InvalidItemLevel,
+		/// The item is past its expiry date
+		ItemIsExpired,
+		/// The CV of the subject holds the maximum number of items
+		TooManySubjectItems,
+		/// The issuer has issued the maximum number of items
+		TooManyIssuerItems,
+		/// The issuer has the maximum number of items in the CV of the subject
+		TooManyItemsFromIssuer,
+		/// The subject of the item erased its account
+		SubjectErased,
+		/// The organization is not approved or not active
+		NotActiveOrg,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			crate::migrations::v1::migrate::<T>()
+		}
+
+		fn on_idle(_n: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			// an item and the day it is filed under
+			let per_item = T::DbWeight::get().reads_writes(3, 3);
+			let limit =
+				(remaining_weight / per_item.max(1)).min(T::MaxExpiriesPerBlock::get() as Weight);
+			if limit == 0 {
+				return 0
+			}
+			Self::expire_items(Self::now(), limit as u32)
+		}
 	}
 
 	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
@@ -143,11 +431,31 @@ pub mod pallet {
 			_org_date: Option<UnixEpoch>,
 			_exp_date: Option<UnixEpoch>,
 			_certificated_id: Option<TypeID>,
+			fields: ItemFields,
 		) -> DispatchResult {
 			// Check that the extrinsic was signed and get the signer.
 			// This function will return an error if the extrinsic is not signed.
 			// https://docs.substrate.io/v3/runtime/origins
-			let who = ensure_signed(origin)?;
+			let who = Self::acting_account(ensure_signed(origin)?)?;
+			fields.validate::<T>(T::MaxItemFieldLength::get() as usize)?;
+			Self::ensure_metadata_length(&_metadata)?;
+			ensure!(!T::AccountRegistry::is_erased(&_account_id), Error::<T>::SubjectErased);
+			// a full CV is cleared by its subject through `dismiss_item`
+			ensure!(
+				Self::items_by_subject(&_account_id).len() < T::MaxItemsPerSubject::get() as usize,
+				Error::<T>::TooManySubjectItems
+			);
+			let issued = Self::items_by_issuer(&who);
+			ensure!(
+				issued.len() < T::MaxItemsPerIssuer::get() as usize,
+				Error::<T>::TooManyIssuerItems
+			);
+			let subject_items = Self::items_by_subject(&_account_id);
+			ensure!(
+				issued.iter().filter(|item_id| subject_items.contains(item_id)).count() <
+					T::MaxItemsPerIssuerAndSubject::get() as usize,
+				Error::<T>::TooManyItemsFromIssuer
+			);
 			let item_id = Self::item_id();
 			let new_item: Item<T> = Item::new(
 				item_id,
@@ -158,9 +466,14 @@ pub mod pallet {
 				_certificated_id,
 				0,
 				_metadata,
+				fields,
 			);
 			<ItemById<T>>::insert(item_id, new_item);
-			<ItemsByAccountId<T>>::mutate(who, |x| x.push(item_id));
+			if let Some(exp_date) = _exp_date {
+				Self::schedule_expiry(item_id, exp_date);
+			}
+			<ItemsBySubject<T>>::mutate(&_account_id, |x| x.push(item_id));
+			<ItemsByIssuer<T>>::mutate(&who, |x| x.push(item_id));
 			<ItemId<T>>::mutate(|n| {
 				*n += 1;
 			});
@@ -170,41 +483,442 @@ pub mod pallet {
 			Ok(())
 		}
 
-		#[pallet::weight(10_000)]
+		#[pallet::weight(Pallet::<T>::remove_item_weight())]
 		pub fn revoke_item(origin: OriginFor<T>, _item_id: TypeID) -> DispatchResult {
 			// Check that the extrinsic was signed and get the signer.
 			// This function will return an error if the extrinsic is not signed.
 			// https://docs.substrate.io/v3/runtime/origins
-			let who = ensure_signed(origin)?;
+			let who = Self::acting_account(ensure_signed(origin)?)?;
 
-			let item_idx = Self::items_by_accountid(&who).iter().position(|x| *x == _item_id);
-			ensure!(item_idx != None, Error::<T>::ItemNotFound);
-			if let Some(iid) = item_idx {
-				<ItemsByAccountId<T>>::mutate(&who, |x| x.swap_remove(iid));
-			}
-			<ItemById<T>>::remove(_item_id);
+			let item = Self::item_by_id(_item_id).ok_or(Error::<T>::ItemNotFound)?;
+			ensure!(item.is_issuer(&who), Error::<T>::NotItemIssuer);
+			Self::remove_item(&item);
 			// Emit an event.
 			Self::deposit_event(Event::RevokeSucceed(_item_id));
 			// Return a successful DispatchResultWithPostInfo
 			Ok(())
 		}
 
-		#[pallet::weight(1000)]
-		pub fn set_status_item(origin: OriginFor<T>, _item_id: TypeID, status: Status) -> DispatchResult {
-			let who = ensure_signed(origin)?;
-			let item_idx = Self::items_by_accountid(&who).iter()
-			.position(|x| { *x == _item_id });
-			ensure!(item_idx != None, Error::<T>::ItemNotFound);
-			match <ItemStatusByItemId<T>>::contains_key(_item_id) {
-				true => {
-					<ItemStatusByItemId<T>>::mutate(_item_id, |x| *x = status);
+		/// Publish a new version of an item, keeping the current one in its history. Must be
+		/// called by the issuer. Changing verified content puts the item back to `Pending` and
+		/// reopens its verification.
+		#[pallet::weight(10_000)]
+		pub fn update_item(
+			origin: OriginFor<T>,
+			item_id: TypeID,
+			metadata: String,
+			org_date: Option<UnixEpoch>,
+			exp_date: Option<UnixEpoch>,
+			certificate_id: Option<TypeID>,
+			fields: ItemFields,
+		) -> DispatchResult {
+			let who = Self::acting_account(ensure_signed(origin)?)?;
+			let mut item = Self::item_by_id(item_id).ok_or(Error::<T>::ItemNotFound)?;
+			ensure!(item.is_issuer(&who), Error::<T>::NotItemIssuer);
+			// erasure is final, the issuer can't put personal data back
+			ensure!(!T::AccountRegistry::is_erased(&item.user_id), Error::<T>::SubjectErased);
+			fields.validate::<T>(T::MaxItemFieldLength::get() as usize)?;
+			Self::ensure_metadata_length(&metadata)?;
+
+			let previous = item.clone();
+			let fields = Some(fields);
+			let content_changed = item.metadata != metadata ||
+				item.org_date != org_date ||
+				item.exp_date != exp_date ||
+				item.certificate_id != certificate_id ||
+				item.fields != fields;
+
+			item.metadata = metadata;
+			item.org_date = org_date;
+			item.certificate_id = certificate_id;
+			item.fields = fields;
+			if item.exp_date != exp_date {
+				item.exp_date = exp_date;
+				item.expired = false;
+				if let Some(exp_date) = exp_date {
+					Self::schedule_expiry(item_id, exp_date);
+				}
+			}
+			item.version += 1;
+			item.updated = Some(WhoAndWhen::<T>::new(who));
+			let version = item.version;
+
+			let pruned = previous.version.checked_sub(T::MaxItemVersions::get()).filter(|v| *v > 0);
+			<ItemVersions<T>>::insert(item_id, previous.version, previous);
+			if let Some(pruned) = pruned {
+				<ItemVersions<T>>::remove(item_id, pruned);
+			}
+			<ItemById<T>>::insert(item_id, item);
+
+			Self::deposit_event(Event::ItemUpdated(item_id, version));
+
+			if content_changed && <VerificationByItemId<T>>::contains_key(item_id) {
+				<VerificationByItemId<T>>::mutate(item_id, |maybe_verification| {
+					if let Some(verification) = maybe_verification {
+						verification.verified = None;
+						verification.reason = Vec::new();
+					}
+				});
+				<ItemStatusByItemId<T>>::insert(item_id, Status::Pending);
+
+				Self::deposit_event(Event::EndorsementReset(item_id));
+			}
+			Ok(())
+		}
+
+		/// Ask `org` to verify an item of the signer's CV. Replaces any earlier request and puts
+		/// the item back to `Pending`. `org` must be an active organization, an old key of it
+		/// stands for its current one.
+		#[pallet::weight(10_000)]
+		pub fn request_verification(
+			origin: OriginFor<T>,
+			item_id: TypeID,
+			org: T::AccountId,
+		) -> DispatchResult {
+			let who = Self::acting_account(ensure_signed(origin)?)?;
+			let item = Self::item_by_id(item_id).ok_or(Error::<T>::ItemNotFound)?;
+			ensure!(item.is_subject(&who), Error::<T>::NotItemSubject);
+			ensure!(!item.is_expired(Self::now()), Error::<T>::ItemIsExpired);
+			let org = T::OrgMembership::current_org_key(&org);
+			ensure!(T::OrgMembership::is_active_org(&org), Error::<T>::NotActiveOrg);
+
+			<VerificationByItemId<T>>::insert(
+				item_id,
+				Verification {
+					org: org.clone(),
+					requested: WhoAndWhen::<T>::new(who),
+					verified: None,
+					reason: Vec::new(),
 				},
-				_ => {
-					<ItemStatusByItemId<T>>::insert(_item_id, status);
+			);
+			<ItemStatusByItemId<T>>::insert(item_id, Status::Pending);
+
+			Self::deposit_event(Event::VerificationRequested(item_id, org));
+			Ok(())
+		}
+
+		/// Allow or deny an item pending verification. Must be called by an admin or verifier of
+		/// the organization the verification was requested from.
+		#[pallet::weight(1000)]
+		pub fn set_status_item(
+			origin: OriginFor<T>,
+			_item_id: TypeID,
+			status: Status,
+			reason: String,
+		) -> DispatchResult {
+			let who = Self::acting_account(ensure_signed(origin)?)?;
+			ensure!(status != Status::Pending, Error::<T>::InvalidStatus);
+			ensure!(
+				reason.len() <= T::MaxItemFieldLength::get() as usize,
+				Error::<T>::ReasonTooLong
+			);
+			let item = Self::item_by_id(_item_id).ok_or(Error::<T>::ItemNotFound)?;
+			ensure!(!item.is_expired(Self::now()), Error::<T>::ItemIsExpired);
+			let mut verification = Self::verification_by_item_id(_item_id)
+				.filter(|v| v.verified.is_none())
+				.ok_or(Error::<T>::NoPendingVerification)?;
+			// the organization may have rotated its key since the request
+			verification.org = T::OrgMembership::current_org_key(&verification.org);
+			ensure!(
+				matches!(
+					T::OrgMembership::member_role(&verification.org, &who),
+					Some(OrgRole::Admin) | Some(OrgRole::Verifier)
+				),
+				Error::<T>::NotOrgVerifier
+			);
+
+			verification.verified = Some(WhoAndWhen::<T>::new(who.clone()));
+			verification.reason = reason;
+			let org = verification.org.clone();
+
+			<VerificationByItemId<T>>::insert(_item_id, verification);
+			<ItemStatusByItemId<T>>::insert(_item_id, status.clone());
+
+			Self::deposit_event(Event::ItemVerified(_item_id, org, who, status));
+			Ok(())
+		}
+
+		/// Hide an item from the signer's CV, or show it again. Only the subject of the item
+		/// can change its visibility.
+		#[pallet::weight(10_000)]
+		pub fn set_item_hidden(
+			origin: OriginFor<T>,
+			item_id: TypeID,
+			hidden: bool,
+		) -> DispatchResult {
+			let who = Self::acting_account(ensure_signed(origin)?)?;
+			let mut item = Self::item_by_id(item_id).ok_or(Error::<T>::ItemNotFound)?;
+			ensure!(item.is_subject(&who), Error::<T>::NotItemSubject);
+
+			item.hidden = hidden;
+			<ItemById<T>>::insert(item_id, item);
+
+			Self::deposit_event(Event::ItemVisibilityChanged(item_id, hidden));
+			Ok(())
+		}
+
+		/// Remove an item from the signer's CV altogether, e.g. one it never agreed to. Only the
+		/// subject of the item can dismiss it.
+		#[pallet::weight(Pallet::<T>::remove_item_weight())]
+		pub fn dismiss_item(origin: OriginFor<T>, item_id: TypeID) -> DispatchResult {
+			let who = Self::acting_account(ensure_signed(origin)?)?;
+			let item = Self::item_by_id(item_id).ok_or(Error::<T>::ItemNotFound)?;
+			ensure!(item.is_subject(&who), Error::<T>::NotItemSubject);
+
+			Self::remove_item(&item);
+
+			Self::deposit_event(Event::ItemDismissed(item_id));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Account a signing key acts for, provided the key may submit items.
+		fn acting_account(key: T::AccountId) -> Result<T::AccountId, DispatchError> {
+			let (who, permission) = T::AccountResolver::resolve(&key);
+			ensure!(permission == KeyPermission::SubmitItems, Error::<T>::KeyNotPermitted);
+			Ok(who)
+		}
+
+		/// Items in the CV of `subject`, leaving out the ones it hid. Nothing is visible while
+		/// its account is deactivated.
+		pub fn visible_items_of(subject: &T::AccountId) -> Vec<Item<T>> {
+			if <DeactivatedSubjects<T>>::contains_key(subject) {
+				return Vec::new()
+			}
+			Self::items_by_subject(subject)
+				.into_iter()
+				.filter_map(Self::item_by_id)
+				.filter(|item| !item.hidden)
+				.collect()
+		}
+
+		/// Visible items in the CV of `subject` that have not expired.
+		pub fn current_items_of(subject: &T::AccountId) -> Vec<Item<T>> {
+			let now = Self::now();
+			Self::visible_items_of(subject)
+				.into_iter()
+				.filter(|item| !item.is_expired(now))
+				.collect()
+		}
+
+		/// Whether an item exists and is past its expiry date.
+		pub fn is_item_expired(item_id: TypeID) -> bool {
+			Self::item_by_id(item_id).map_or(false, |item| item.is_expired(Self::now()))
+		}
+
+		/// Versions of an item still kept, oldest first and ending with the current one.
+		pub fn item_history(item_id: TypeID) -> Vec<Item<T>> {
+			let mut versions: Vec<Item<T>> =
+				<ItemVersions<T>>::iter_prefix_values(item_id).collect();
+			versions.sort_by_key(|item| item.version);
+			versions.extend(Self::item_by_id(item_id));
+			versions
+		}
+
+		/// Current time in milliseconds, as set by `pallet_timestamp`.
+		pub fn now() -> UnixEpoch {
+			<pallet_timestamp::Pallet<T>>::now().saturated_into()
+		}
+
+		/// Weight of `remove_item`, its history being at most `MaxItemVersions` long.
+		fn remove_item_weight() -> Weight {
+			10_000 + T::DbWeight::get().reads_writes(4, 5 + T::MaxItemVersions::get() as Weight)
+		}
+
+		/// Weight of scrubbing `items` items of an erased account and `versions` prior
+		/// versions of them.
+		fn erase_weight(items: Weight, versions: Weight) -> Weight {
+			T::DbWeight::get().reads_writes(1 + items + 2 * versions, items + versions)
+		}
+
+		/// Weight of moving `items` items of a recovered account to its new key.
+		fn rebound_weight(items: Weight) -> Weight {
+			T::DbWeight::get().reads_writes(5 + items, 7 + items)
+		}
+
+		/// Remove an item with its history, verification and index entries.
+		fn remove_item(item: &Item<T>) {
+			let item_id = item.item_id;
+			<ItemsBySubject<T>>::mutate(&item.user_id, |x| x.retain(|id| *id != item_id));
+			<ItemsByIssuer<T>>::mutate(&item.created.account, |x| x.retain(|id| *id != item_id));
+			<ItemStatusByItemId<T>>::remove(item_id);
+			<VerificationByItemId<T>>::remove(item_id);
+			<ItemVersions<T>>::remove_prefix(item_id, None);
+			<ItemById<T>>::remove(item_id);
+		}
+
+		pub(crate) fn schedule_expiry(item_id: TypeID, exp_date: UnixEpoch) {
+			// dates already past are filed under today, the sweep never walks back
+			let bucket = (exp_date / EXPIRY_BUCKET).max(Self::now() / EXPIRY_BUCKET);
+			<ExpiringItems<T>>::mutate(bucket, |x| x.push(item_id));
+			<NextExpiryBucket<T>>::mutate(|next| {
+				*next = Some(next.map_or(bucket, |next| next.min(bucket)));
+			});
+		}
+
+		/// Mark up to `limit` items expired at `now`, walking `ExpiringItems` in order. Days
+		/// without items don't count towards `limit`, but no more than `limit` days are walked.
+		fn expire_items(now: UnixEpoch, limit: u32) -> Weight {
+			let mut bucket = match Self::next_expiry_bucket() {
+				Some(bucket) => bucket,
+				None => return T::DbWeight::get().reads(1),
+			};
+			let first = bucket;
+			let mut budget = limit;
+			let mut days = limit;
+			let mut reads = 1;
+			let mut writes = 0;
+
+			while bucket <= now / EXPIRY_BUCKET && budget > 0 && days > 0 {
+				let mut pending = <ExpiringItems<T>>::take(bucket);
+				reads += 1;
+				writes += 1;
+				days -= 1;
+
+				// items revoked or already expired are dropped, later ones wait for their time
+				pending.retain(|item_id| {
+					if budget == 0 {
+						return true
+					}
+					budget -= 1;
+					reads += 1;
+					<ItemById<T>>::mutate(item_id, |maybe_item| match maybe_item {
+						Some(item) if !item.expired => match item.exp_date {
+							Some(exp_date) if exp_date <= now => {
+								item.expired = true;
+								writes += 1;
+								Self::deposit_event(Event::ItemExpired(*item_id));
+								false
+							},
+							Some(exp_date) => exp_date / EXPIRY_BUCKET == bucket,
+							None => false,
+						},
+						_ => false,
+					})
+				});
+
+				if !pending.is_empty() {
+					<ExpiringItems<T>>::insert(bucket, pending);
+					break
 				}
+				bucket += 1;
 			}
-			Self::deposit_event(Event::SetStatusSucceed(_item_id));
+
+			if bucket != first {
+				<NextExpiryBucket<T>>::put(bucket);
+				writes += 1;
+			}
+			T::DbWeight::get().reads_writes(reads, writes)
+		}
+
+		fn ensure_metadata_length(metadata: &[u8]) -> DispatchResult {
+			ensure!(
+				metadata.len() <= T::MaxItemMetadataLength::get() as usize,
+				Error::<T>::ItemMetadataTooLong
+			);
 			Ok(())
 		}
+
+		/// Visible items of a given category in the CV of `subject`.
+		pub fn visible_items_of_type(subject: &T::AccountId, item_type: ItemType) -> Vec<Item<T>> {
+			Self::visible_items_of(subject)
+				.into_iter()
+				.filter(|item| item.item_type() == Some(item_type))
+				.collect()
+		}
+	}
+
+	impl<T: Config> OnAccountErased<T::AccountId> for Pallet<T> {
+		fn on_account_erased(who: &T::AccountId) -> Weight {
+			let mut items = 0;
+			let mut versions = 0;
+			// items stay in place so issuers keep their history, only the personal data goes
+			for item_id in Self::items_by_subject(who) {
+				items += 1;
+				<ItemById<T>>::mutate(item_id, |maybe_item| {
+					if let Some(item) = maybe_item {
+						item.metadata = Vec::new();
+						item.fields = None;
+					}
+				});
+				let prior: Vec<u32> =
+					<ItemVersions<T>>::iter_prefix(item_id).map(|(version, _)| version).collect();
+				versions += prior.len() as Weight;
+				for version in prior {
+					<ItemVersions<T>>::mutate(item_id, version, |maybe_item| {
+						if let Some(item) = maybe_item {
+							item.metadata = Vec::new();
+							item.fields = None;
+						}
+					});
+				}
+			}
+
+			Self::deposit_event(Event::AccountItemsErased(who.clone()));
+			Self::erase_weight(items, versions)
+		}
+
+		fn max_erase_weight() -> Weight {
+			let items = T::MaxItemsPerSubject::get() as Weight;
+			Self::erase_weight(items, items * T::MaxItemVersions::get() as Weight)
+		}
+	}
+
+	impl<T: Config> OnAccountStatusChange<T::AccountId> for Pallet<T> {
+		fn on_account_deactivated(who: &T::AccountId) {
+			<DeactivatedSubjects<T>>::insert(who, ());
+		}
+
+		fn on_account_reactivated(who: &T::AccountId) {
+			<DeactivatedSubjects<T>>::remove(who);
+		}
+	}
+
+	impl<T: Config> OnAccountRebound<T::AccountId> for Pallet<T> {
+		fn on_account_rebound(old: &T::AccountId, new: &T::AccountId) -> Weight {
+			if <DeactivatedSubjects<T>>::take(old).is_some() {
+				<DeactivatedSubjects<T>>::insert(new, ());
+			}
+
+			// subject items also carry the verification the subject requested
+			let subject_of = <ItemsBySubject<T>>::take(old);
+			let mut items = 2 * subject_of.len() as Weight;
+			for item_id in subject_of.iter() {
+				<ItemById<T>>::mutate(item_id, |maybe_item| {
+					if let Some(item) = maybe_item {
+						item.user_id = new.clone();
+					}
+				});
+				<VerificationByItemId<T>>::mutate(item_id, |maybe_verification| {
+					if let Some(verification) = maybe_verification {
+						if verification.requested.account == *old {
+							verification.requested.account = new.clone();
+						}
+					}
+				});
+			}
+			<ItemsBySubject<T>>::mutate(new, |x| x.extend(subject_of));
+
+			let issuer_of = <ItemsByIssuer<T>>::take(old);
+			items += issuer_of.len() as Weight;
+			for item_id in issuer_of.iter() {
+				<ItemById<T>>::mutate(item_id, |maybe_item| {
+					if let Some(item) = maybe_item {
+						item.created.account = new.clone();
+					}
+				});
+			}
+			<ItemsByIssuer<T>>::mutate(new, |x| x.extend(issuer_of));
+
+			Self::deposit_event(Event::AccountItemsRebound(old.clone(), new.clone()));
+			Self::rebound_weight(items)
+		}
+
+		fn max_rebound_weight() -> Weight {
+			Self::rebound_weight(
+				2 * T::MaxItemsPerSubject::get() as Weight + T::MaxItemsPerIssuer::get() as Weight,
+			)
+		}
 	}
-}
\ No newline at end of file
+}
@@ -0,0 +1,70 @@
+//! Storage migrations of the cv pallet.
+
+use super::*;
+use frame_support::{
+	inherent::Vec,
+	pallet_prelude::*,
+	storage::migration::remove_storage_prefix,
+	traits::{PalletInfoAccess, StorageVersion},
+};
+use pallet_utils::{String, TypeID, UnixEpoch, WhoAndWhen};
+
+/// Moves items to the layout with structured fields, visibility, expiry and versions, and
+/// replaces `ItemsByAccountId` with the subject and issuer indexes.
+pub mod v1 {
+	use super::*;
+
+	/// Item as stored before version 1, the layout every deployed chain still has.
+	#[derive(Decode)]
+	struct OldItem<T: Config> {
+		item_id: TypeID,
+		user_id: T::AccountId,
+		created: WhoAndWhen<T>,
+		org_date: Option<UnixEpoch>,
+		exp_date: Option<UnixEpoch>,
+		certificate_id: Option<TypeID>,
+		score: u32,
+		metadata: String,
+	}
+
+	pub fn migrate<T: Config>() -> Weight {
+		if StorageVersion::get::<Pallet<T>>() >= 1 {
+			return T::DbWeight::get().reads(1)
+		}
+
+		let mut items = Vec::new();
+		ItemById::<T>::translate::<OldItem<T>, _>(|_, old| {
+			let issuer = old.created.account.clone();
+			items.push((old.item_id, old.user_id.clone(), issuer, old.exp_date));
+			Some(Item {
+				item_id: old.item_id,
+				user_id: old.user_id,
+				created: old.created,
+				org_date: old.org_date,
+				exp_date: old.exp_date,
+				certificate_id: old.certificate_id,
+				score: old.score,
+				metadata: old.metadata,
+				hidden: false,
+				fields: None,
+				expired: false,
+				version: 1,
+				updated: None,
+			})
+		});
+
+		// the old index mixed subjects and issuers under the issuer, rebuild both from the items
+		remove_storage_prefix(<Pallet<T>>::name().as_bytes(), b"ItemsByAccountId", &[]);
+		let count = items.len() as Weight;
+		for (item_id, subject, issuer, exp_date) in items {
+			ItemsBySubject::<T>::append(subject, item_id);
+			ItemsByIssuer::<T>::append(issuer, item_id);
+			if let Some(exp_date) = exp_date {
+				Pallet::<T>::schedule_expiry(item_id, exp_date);
+			}
+		}
+
+		STORAGE_VERSION.put::<Pallet<T>>();
+		T::DbWeight::get().reads_writes(4 * count + 2, 6 * count + 2)
+	}
+}
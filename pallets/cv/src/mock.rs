@@ -1,11 +1,18 @@
-use crate as pallet_template;
+//! mock setup for testing pallet-cv functionalities
+
+use crate as pallet_cv;
 use frame_support::parameter_types;
 use frame_system as system;
+use pallet_utils::{
+	traits::{AccountRegistry, OrgMembership},
+	OrgRole,
+};
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
 };
+use std::cell::RefCell;
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -18,13 +25,31 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
-		TemplateModule: pallet_template::{Pallet, Call, Storage, Event<T>},
+		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Utils: pallet_utils::{Pallet, Call, Storage, Event<T>},
+		CvModule: pallet_cv::{Pallet, Call, Storage, Event<T>},
 	}
 );
 
+/// Milliseconds in a day, the length of the buckets of the expiry index.
+pub const DAY: u64 = 24 * 60 * 60 * 1000;
+
 parameter_types! {
 	pub const BlockHashCount: u64 = 250;
 	pub const SS58Prefix: u8 = 42;
+	pub const MinimumPeriod: u64 = 5;
+	pub const ExistentialDeposit: u64 = 1;
+	pub const HandleDeposit: u64 = 10;
+	pub const MinHandleLen: u32 = 3;
+	pub const MaxHandleLen: u32 = 16;
+	pub const MaxItemMetadataLength: u32 = 64;
+	pub const MaxItemFieldLength: u32 = 32;
+	pub const MaxExpiriesPerBlock: u32 = 2;
+	pub const MaxItemsPerSubject: u32 = 3;
+	pub const MaxItemsPerIssuer: u32 = 4;
+	pub const MaxItemsPerIssuerAndSubject: u32 = 2;
+	pub const MaxItemVersions: u32 = 2;
 }
 
 impl system::Config for Test {
@@ -45,7 +70,7 @@ impl system::Config for Test {
 	type BlockHashCount = BlockHashCount;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -53,11 +78,114 @@ impl system::Config for Test {
 	type OnSetCode = ();
 }
 
-impl pallet_template::Config for Test {
+impl pallet_timestamp::Config for Test {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = MinimumPeriod;
+	type WeightInfo = ();
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+impl pallet_utils::Config for Test {
 	type Event = Event;
+	type Currency = Balances;
+	type HandleDeposit = HandleDeposit;
+	type MinHandleLen = MinHandleLen;
+	type MaxHandleLen = MaxHandleLen;
+}
+
+impl pallet_cv::Config for Test {
+	type Event = Event;
+	type AccountResolver = ();
+	type AccountRegistry = MockRegistry;
+	type OrgMembership = MockOrgs;
+	type MaxItemFieldLength = MaxItemFieldLength;
+	type MaxItemMetadataLength = MaxItemMetadataLength;
+	type MaxExpiriesPerBlock = MaxExpiriesPerBlock;
+	type MaxItemsPerSubject = MaxItemsPerSubject;
+	type MaxItemsPerIssuer = MaxItemsPerIssuer;
+	type MaxItemsPerIssuerAndSubject = MaxItemsPerIssuerAndSubject;
+	type MaxItemVersions = MaxItemVersions;
+}
+
+thread_local! {
+	static ERASED: RefCell<Vec<u64>> = RefCell::new(Vec::new());
+	static ORG_KEY: RefCell<u64> = RefCell::new(ORG);
+	static OLD_ORG_KEYS: RefCell<Vec<u64>> = RefCell::new(Vec::new());
+}
+
+/// The only active organization, until it rotates its key.
+pub const ORG: u64 = 100;
+/// Verifier of `ORG`.
+pub const VERIFIER: u64 = 101;
+/// Issuer of `ORG`, which can't verify items.
+pub const ORG_ISSUER: u64 = 102;
+
+/// Accounts erased with `erase`.
+pub struct MockRegistry;
+
+impl AccountRegistry<u64> for MockRegistry {
+	fn is_erased(who: &u64) -> bool {
+		ERASED.with(|erased| erased.borrow().contains(who))
+	}
+}
+
+pub fn erase(who: u64) {
+	ERASED.with(|erased| erased.borrow_mut().push(who));
+}
+
+/// A single organization, registered as `ORG`, whose key moves with `rotate_org_key`.
+pub struct MockOrgs;
+
+impl OrgMembership<u64> for MockOrgs {
+	fn current_org_key(org: &u64) -> u64 {
+		if OLD_ORG_KEYS.with(|keys| keys.borrow().contains(org)) {
+			ORG_KEY.with(|key| *key.borrow())
+		} else {
+			*org
+		}
+	}
+
+	fn is_active_org(org: &u64) -> bool {
+		ORG_KEY.with(|key| *key.borrow() == *org)
+	}
+
+	fn member_role(org: &u64, who: &u64) -> Option<OrgRole> {
+		if !Self::is_active_org(org) {
+			return None
+		}
+		match *who {
+			VERIFIER => Some(OrgRole::Verifier),
+			ORG_ISSUER => Some(OrgRole::Issuer),
+			who if who == *org => Some(OrgRole::Admin),
+			_ => None,
+		}
+	}
+}
+
+pub fn rotate_org_key(new: u64) {
+	let old = ORG_KEY.with(|key| key.replace(new));
+	OLD_ORG_KEYS.with(|keys| keys.borrow_mut().push(old));
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	ERASED.with(|erased| erased.borrow_mut().clear());
+	ORG_KEY.with(|key| *key.borrow_mut() = ORG);
+	OLD_ORG_KEYS.with(|keys| keys.borrow_mut().clear());
+	let t = system::GenesisConfig::default().build_storage::<Test>().unwrap().into();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
 }
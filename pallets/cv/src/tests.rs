@@ -1,20 +1,493 @@
-use crate::{mock::*, Error};
-use frame_support::{assert_noop, assert_ok};
+//! pallet-cv functionalities test
+use crate::{mock::*, Error, ItemFields, ItemType, Status};
+use frame_support::{assert_noop, assert_ok, dispatch::DispatchResult, traits::Hooks};
+use pallet_utils::traits::OnAccountRebound;
+
+fn str2vec(s: &str) -> Vec<u8> {
+	s.as_bytes().to_vec()
+}
+
+fn skill(name: &str) -> ItemFields {
+	ItemFields::Skill { name: str2vec(name), level: 3 }
+}
+
+fn create_skill(issuer: u64, subject: u64, name: &str) -> DispatchResult {
+	CvModule::create_item(Origin::signed(issuer), subject, vec![], None, None, None, skill(name))
+}
+
+fn create_with(issuer: u64, subject: u64, fields: ItemFields) -> DispatchResult {
+	CvModule::create_item(Origin::signed(issuer), subject, vec![], None, None, None, fields)
+}
+
+fn education(field_of_study: &str, start: u64, end: u64) -> ItemFields {
+	ItemFields::Education {
+		degree: str2vec("BSc"),
+		institution: str2vec("MIT"),
+		field_of_study: str2vec(field_of_study),
+		start: Some(start),
+		end: Some(end),
+	}
+}
+
+#[test]
+fn items_should_be_validated_and_bounded() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(create_skill(10, 20, ""), Error::<Test>::MissingItemField);
+		assert_noop!(
+			CvModule::create_item(
+				Origin::signed(10),
+				20,
+				vec![],
+				None,
+				None,
+				None,
+				ItemFields::Skill { name: str2vec("rust"), level: 6 }
+			),
+			Error::<Test>::InvalidItemLevel
+		);
+		assert_noop!(
+			CvModule::create_item(
+				Origin::signed(10),
+				20,
+				vec![b'a'; 65],
+				None,
+				None,
+				None,
+				skill("rust")
+			),
+			Error::<Test>::ItemMetadataTooLong
+		);
+
+		assert_ok!(create_skill(10, 20, "rust"));
+
+		System::assert_last_event(Event::CvModule(crate::Event::CreateSucceed(0)));
+
+		assert_ok!(create_skill(10, 20, "go"));
+
+		// a single issuer can't fill the CV on its own
+		assert_noop!(create_skill(10, 20, "c"), Error::<Test>::TooManyItemsFromIssuer);
+
+		assert_ok!(create_skill(11, 20, "c"));
+
+		assert_noop!(create_skill(11, 20, "zig"), Error::<Test>::TooManySubjectItems);
+
+		assert_ok!(create_skill(10, 30, "rust"));
+		assert_ok!(create_skill(10, 30, "go"));
+
+		assert_noop!(create_skill(10, 40, "rust"), Error::<Test>::TooManyIssuerItems);
+
+		// revoking an item frees room for the issuer
+		assert_ok!(CvModule::revoke_item(Origin::signed(10), 3));
+
+		assert_eq!(CvModule::items_by_issuer(10), vec![0, 1, 4]);
+		assert_ok!(create_skill(10, 40, "rust"));
+	});
+}
+
+#[test]
+fn subjects_should_dismiss_items_from_their_cv() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_skill(10, 20, "rust"));
+		assert_ok!(create_skill(10, 20, "go"));
+		assert_ok!(create_skill(11, 20, "c"));
+
+		assert_noop!(CvModule::dismiss_item(Origin::signed(10), 0), Error::<Test>::NotItemSubject);
+
+		assert_ok!(CvModule::dismiss_item(Origin::signed(20), 0));
+
+		System::assert_last_event(Event::CvModule(crate::Event::ItemDismissed(0)));
+		assert!(CvModule::item_by_id(0).is_none());
+		assert_eq!(CvModule::items_by_subject(20), vec![1, 2]);
+		assert_eq!(CvModule::items_by_issuer(10), vec![1]);
+		assert_noop!(CvModule::dismiss_item(Origin::signed(20), 0), Error::<Test>::ItemNotFound);
+
+		// the room freed in the CV can be filled again
+		assert_ok!(create_skill(11, 20, "zig"));
+	});
+}
 
 #[test]
-fn it_works_for_default_value() {
+fn items_should_not_be_issued_to_erased_subjects() {
 	new_test_ext().execute_with(|| {
-		// Dispatch a signed extrinsic.
-		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
-		// Read pallet storage and assert an expected result.
-		assert_eq!(TemplateModule::something(), Some(42));
+		erase(20);
+
+		assert_noop!(create_skill(10, 20, "rust"), Error::<Test>::SubjectErased);
+		assert_eq!(CvModule::item_id(), 0);
 	});
 }
 
 #[test]
-fn correct_error_for_none_value() {
+fn verifications_should_move_through_their_states() {
 	new_test_ext().execute_with(|| {
-		// Ensure the expected error is thrown when no value is present.
-		assert_noop!(TemplateModule::cause_error(Origin::signed(1)), Error::<Test>::NoneValue);
+		assert_ok!(create_skill(10, 20, "rust"));
+
+		assert_eq!(CvModule::item_status_by_item_id(0), Status::Pending);
+		assert_noop!(
+			CvModule::request_verification(Origin::signed(30), 0, ORG),
+			Error::<Test>::NotItemSubject
+		);
+		assert_noop!(
+			CvModule::request_verification(Origin::signed(20), 0, 200),
+			Error::<Test>::NotActiveOrg
+		);
+		assert_noop!(
+			CvModule::set_status_item(Origin::signed(VERIFIER), 0, Status::Allow, vec![]),
+			Error::<Test>::NoPendingVerification
+		);
+
+		assert_ok!(CvModule::request_verification(Origin::signed(20), 0, ORG));
+
+		System::assert_last_event(Event::CvModule(crate::Event::VerificationRequested(0, ORG)));
+		assert_noop!(
+			CvModule::set_status_item(Origin::signed(VERIFIER), 0, Status::Pending, vec![]),
+			Error::<Test>::InvalidStatus
+		);
+		assert_noop!(
+			CvModule::set_status_item(Origin::signed(ORG_ISSUER), 0, Status::Allow, vec![]),
+			Error::<Test>::NotOrgVerifier
+		);
+
+		assert_noop!(
+			CvModule::set_status_item(Origin::signed(VERIFIER), 0, Status::Allow, vec![b'a'; 33]),
+			Error::<Test>::ReasonTooLong
+		);
+
+		// the organization rotates its key while the request is pending
+		rotate_org_key(110);
+
+		assert_ok!(CvModule::set_status_item(
+			Origin::signed(VERIFIER),
+			0,
+			Status::Allow,
+			str2vec("checked")
+		));
+
+		System::assert_last_event(Event::CvModule(crate::Event::ItemVerified(
+			0,
+			110,
+			VERIFIER,
+			Status::Allow,
+		)));
+		assert_eq!(CvModule::item_status_by_item_id(0), Status::Allow);
+		assert_eq!(CvModule::verification_by_item_id(0).map(|v| v.org), Some(110));
+		assert_noop!(
+			CvModule::set_status_item(Origin::signed(VERIFIER), 0, Status::Deny, vec![]),
+			Error::<Test>::NoPendingVerification
+		);
+
+		// an update keeping the content keeps the endorsement
+		assert_ok!(CvModule::update_item(
+			Origin::signed(10),
+			0,
+			vec![],
+			None,
+			None,
+			None,
+			skill("rust")
+		));
+
+		assert_eq!(CvModule::item_status_by_item_id(0), Status::Allow);
+
+		// changing verified content reopens the verification
+		assert_ok!(CvModule::update_item(
+			Origin::signed(10),
+			0,
+			vec![],
+			None,
+			None,
+			None,
+			skill("go")
+		));
+
+		System::assert_last_event(Event::CvModule(crate::Event::EndorsementReset(0)));
+		assert_eq!(CvModule::item_status_by_item_id(0), Status::Pending);
+		assert!(CvModule::verification_by_item_id(0).unwrap().verified.is_none());
+
+		// requests made to an old key go to the current one, which acts as an admin
+		assert_ok!(CvModule::request_verification(Origin::signed(20), 0, ORG));
+
+		System::assert_last_event(Event::CvModule(crate::Event::VerificationRequested(0, 110)));
+
+		assert_ok!(CvModule::set_status_item(
+			Origin::signed(110),
+			0,
+			Status::Deny,
+			str2vec("not held")
+		));
+
+		assert_eq!(CvModule::item_status_by_item_id(0), Status::Deny);
+	});
+}
+
+fn create_expiring(subject: u64, exp_date: u64) -> DispatchResult {
+	CvModule::create_item(
+		Origin::signed(10),
+		subject,
+		vec![],
+		None,
+		Some(exp_date),
+		None,
+		skill("rust"),
+	)
+}
+
+#[test]
+fn expiry_sweep_should_mark_items_expired_in_order() {
+	new_test_ext().execute_with(|| {
+		Timestamp::set_timestamp(10 * DAY);
+
+		assert_ok!(create_expiring(20, 10 * DAY + 1_000));
+		assert_ok!(create_expiring(20, 12 * DAY));
+		// dates already past are filed under today rather than stalling the sweep
+		assert_ok!(create_expiring(30, 3 * DAY));
+
+		assert_eq!(CvModule::next_expiry_bucket(), Some(10));
+		assert_eq!(CvModule::items_expiring_in(10), vec![0, 2]);
+		assert!(CvModule::is_item_expired(2));
+		assert!(CvModule::current_items_of(&30).is_empty());
+		assert_noop!(
+			CvModule::request_verification(Origin::signed(30), 2, ORG),
+			Error::<Test>::ItemIsExpired
+		);
+
+		// no weight left, nothing is touched
+		assert_eq!(CvModule::on_idle(1, 0), 0);
+		assert!(!CvModule::item_by_id(2).unwrap().expired);
+
+		CvModule::on_idle(1, u64::MAX);
+
+		System::assert_last_event(Event::CvModule(crate::Event::ItemExpired(2)));
+		assert!(CvModule::item_by_id(2).unwrap().expired);
+		assert!(!CvModule::item_by_id(0).unwrap().expired);
+		assert_eq!(CvModule::items_expiring_in(10), vec![0]);
+		assert_eq!(CvModule::next_expiry_bucket(), Some(10));
+
+		// days without items are walked over, at most `MaxExpiriesPerBlock` of them a block
+		Timestamp::set_timestamp(11 * DAY);
+		CvModule::on_idle(2, u64::MAX);
+
+		assert!(CvModule::item_by_id(0).unwrap().expired);
+		assert_eq!(CvModule::next_expiry_bucket(), Some(12));
+		assert_eq!(CvModule::current_items_of(&20).len(), 1);
+
+		Timestamp::set_timestamp(12 * DAY);
+		CvModule::on_idle(3, u64::MAX);
+
+		System::assert_last_event(Event::CvModule(crate::Event::ItemExpired(1)));
+		assert!(CvModule::item_by_id(1).unwrap().expired);
+		assert!(CvModule::current_items_of(&20).is_empty());
+		assert_eq!(CvModule::visible_items_of(&20).len(), 2);
+	});
+}
+
+#[test]
+fn item_history_should_keep_the_latest_versions() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_skill(10, 20, "rust"));
+
+		assert_noop!(
+			CvModule::update_item(Origin::signed(20), 0, vec![], None, None, None, skill("go")),
+			Error::<Test>::NotItemIssuer
+		);
+		assert_noop!(
+			CvModule::update_item(
+				Origin::signed(10),
+				0,
+				vec![b'a'; 65],
+				None,
+				None,
+				None,
+				skill("go")
+			),
+			Error::<Test>::ItemMetadataTooLong
+		);
+
+		for name in ["go", "c", "zig"] {
+			assert_ok!(CvModule::update_item(
+				Origin::signed(10),
+				0,
+				vec![],
+				None,
+				None,
+				None,
+				skill(name)
+			));
+		}
+
+		System::assert_last_event(Event::CvModule(crate::Event::ItemUpdated(0, 4)));
+		// only the latest `MaxItemVersions` prior versions are kept
+		let history = CvModule::item_history(0);
+		assert_eq!(history.iter().map(|item| item.version).collect::<Vec<_>>(), vec![2, 3, 4]);
+		assert_eq!(history[0].fields, Some(skill("go")));
+		assert_eq!(history[2].fields, Some(skill("zig")));
+		assert!(history[0].updated.is_some());
+		assert!(CvModule::item_version(0, 1).is_none());
+
+		// revoking the item drops its history
+		assert_ok!(create_skill(10, 20, "go"));
+		assert_ok!(CvModule::update_item(
+			Origin::signed(10),
+			1,
+			vec![],
+			None,
+			None,
+			None,
+			skill("c")
+		));
+		assert_ok!(CvModule::revoke_item(Origin::signed(10), 1));
+
+		assert!(CvModule::item_history(1).is_empty());
+	});
+}
+
+#[test]
+fn items_of_erased_subjects_should_not_be_updated() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_skill(10, 20, "rust"));
+
+		erase(20);
+
+		assert_noop!(
+			CvModule::update_item(Origin::signed(10), 0, vec![], None, None, None, skill("go")),
+			Error::<Test>::SubjectErased
+		);
+	});
+}
+
+#[test]
+fn item_fields_should_be_validated_per_type() {
+	new_test_ext().execute_with(|| {
+		let too_long = "a".repeat(33);
+
+		// optional fields are bounded as well
+		assert_noop!(
+			create_with(10, 20, education(&too_long, 1, 2)),
+			Error::<Test>::ItemFieldTooLong
+		);
+		assert_noop!(
+			create_with(10, 20, education("physics", 2, 1)),
+			Error::<Test>::InvalidItemPeriod
+		);
+		assert_noop!(
+			create_with(
+				10,
+				20,
+				ItemFields::Employment {
+					job_title: str2vec("engineer"),
+					employer: vec![],
+					start: None,
+					end: None,
+				}
+			),
+			Error::<Test>::MissingItemField
+		);
+		assert_noop!(
+			create_with(
+				10,
+				20,
+				ItemFields::Employment {
+					job_title: str2vec("engineer"),
+					employer: str2vec("acme"),
+					start: Some(5),
+					end: Some(4),
+				}
+			),
+			Error::<Test>::InvalidItemPeriod
+		);
+		assert_noop!(
+			create_with(
+				10,
+				20,
+				ItemFields::Language { language: str2vec("french"), proficiency: 0 }
+			),
+			Error::<Test>::InvalidItemLevel
+		);
+		assert_noop!(
+			create_with(
+				10,
+				20,
+				ItemFields::Publication {
+					title: str2vec("paper"),
+					publisher: vec![],
+					published: None,
+					url: str2vec(&too_long),
+				}
+			),
+			Error::<Test>::ItemFieldTooLong
+		);
+		assert_noop!(
+			create_with(
+				10,
+				20,
+				ItemFields::Award { title: str2vec("prize"), awarded_by: vec![], awarded: None }
+			),
+			Error::<Test>::MissingItemField
+		);
+		assert_eq!(CvModule::item_id(), 0);
+
+		// a period may start and end on the same date, optional fields may be empty
+		assert_ok!(create_with(10, 20, education("", 1, 1)));
+		assert_ok!(create_with(
+			10,
+			20,
+			ItemFields::Publication {
+				title: str2vec("paper"),
+				publisher: vec![],
+				published: Some(1),
+				url: vec![],
+			}
+		));
+		assert_ok!(create_with(
+			11,
+			20,
+			ItemFields::Language { language: str2vec("french"), proficiency: 5 }
+		));
+	});
+}
+
+#[test]
+fn visible_items_should_be_filtered_by_type() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_skill(10, 20, "rust"));
+		assert_ok!(create_with(10, 20, education("physics", 1, 2)));
+		assert_ok!(create_skill(11, 20, "go"));
+
+		let ids = |item_type| {
+			CvModule::visible_items_of_type(&20, item_type)
+				.into_iter()
+				.map(|item| item.item_id)
+				.collect::<Vec<_>>()
+		};
+
+		assert_eq!(ids(ItemType::Skill), vec![0, 2]);
+		assert_eq!(ids(ItemType::Education), vec![1]);
+		assert!(ids(ItemType::Award).is_empty());
+
+		// hidden items are left out
+		assert_ok!(CvModule::set_item_hidden(Origin::signed(20), 0, true));
+
+		assert_eq!(ids(ItemType::Skill), vec![2]);
+		assert!(CvModule::visible_items_of_type(&30, ItemType::Skill).is_empty());
+	});
+}
+
+#[test]
+fn rebound_accounts_should_keep_their_items_and_requests() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_skill(10, 20, "rust"));
+		assert_ok!(create_skill(20, 30, "go"));
+		assert_ok!(CvModule::request_verification(Origin::signed(20), 0, ORG));
+
+		CvModule::on_account_rebound(&20, &25);
+
+		System::assert_last_event(Event::CvModule(crate::Event::AccountItemsRebound(20, 25)));
+		assert_eq!(CvModule::items_by_subject(25), vec![0]);
+		assert_eq!(CvModule::items_by_issuer(25), vec![1]);
+		assert_eq!(CvModule::item_by_id(0).unwrap().user_id, 25);
+		assert_eq!(CvModule::item_by_id(1).unwrap().created.account, 25);
+		assert_eq!(CvModule::verification_by_item_id(0).unwrap().requested.account, 25);
+
+		// the pending request can still be answered
+		assert_ok!(CvModule::set_status_item(Origin::signed(VERIFIER), 0, Status::Allow, vec![]));
 	});
 }
@@ -0,0 +1,51 @@
+//! Runtime API definition for querying the sys-man hierarchy.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The `too_many_arguments` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::too_many_arguments)]
+// The `unnecessary_mut_passed` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+pub use pallet_sys_man::{AuditId, OperationType};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait SysManApi<AccountId, Account, AuditEntry> where
+		AccountId: Codec,
+		Account: Codec,
+		AuditEntry: Codec,
+	{
+		/// Approved system manager or organization record of `id`.
+		fn account(id: AccountId, op_type: OperationType) -> Option<Account>;
+
+		/// Revoked system manager or organization record of `id`.
+		fn revoked_account(id: AccountId, op_type: OperationType) -> Option<Account>;
+
+		/// Active system managers appointed by `id`.
+		fn children(id: AccountId) -> Vec<AccountId>;
+
+		/// System managers above `id`, from its parent up to the root authority.
+		fn ancestors(id: AccountId) -> Vec<AccountId>;
+
+		/// Up to `limit` active system managers below `id`, in breadth-first order.
+		fn subtree(id: AccountId, limit: u32) -> Vec<AccountId>;
+
+		/// Page of active or revoked organizations, starting after `start_after`.
+		fn orgs(
+			revoked: bool,
+			start_after: Option<AccountId>,
+			limit: u32,
+		) -> Vec<(AccountId, Account)>;
+
+		/// Whether `id` is an active system manager that can exercise its authority.
+		fn is_authority(id: AccountId) -> bool;
+
+		/// Page of up to `limit` audit log entries, oldest first, starting at `start`.
+		fn audit_log(start: AuditId, limit: u32) -> Vec<(AuditId, AuditEntry)>;
+
+		/// Page of up to `limit` audit log entries `account` took part in, oldest first,
+		/// skipping the first `start` of them.
+		fn audit_log_of(account: AccountId, start: u32, limit: u32) -> Vec<(AuditId, AuditEntry)>;
+	}
+}
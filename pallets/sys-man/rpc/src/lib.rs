@@ -0,0 +1,227 @@
+//! RPC interface for querying the sys-man hierarchy.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+pub use pallet_sys_man_rpc_runtime_api::{AuditId, OperationType, SysManApi as SysManRuntimeApi};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+/// Error code for failures of the runtime API call.
+const RUNTIME_ERROR: i64 = 1;
+
+#[rpc]
+pub trait SysManApi<BlockHash, AccountId, Account, AuditEntry> {
+	/// Approved system manager or organization record of `id`.
+	#[rpc(name = "sysMan_account")]
+	fn account(
+		&self,
+		id: AccountId,
+		op_type: OperationType,
+		at: Option<BlockHash>,
+	) -> Result<Option<Account>>;
+
+	/// Revoked system manager or organization record of `id`.
+	#[rpc(name = "sysMan_revokedAccount")]
+	fn revoked_account(
+		&self,
+		id: AccountId,
+		op_type: OperationType,
+		at: Option<BlockHash>,
+	) -> Result<Option<Account>>;
+
+	/// Active system managers appointed by `id`.
+	#[rpc(name = "sysMan_children")]
+	fn children(&self, id: AccountId, at: Option<BlockHash>) -> Result<Vec<AccountId>>;
+
+	/// System managers above `id`, from its parent up to the root authority.
+	#[rpc(name = "sysMan_ancestors")]
+	fn ancestors(&self, id: AccountId, at: Option<BlockHash>) -> Result<Vec<AccountId>>;
+
+	/// Up to `limit` active system managers below `id`, in breadth-first order.
+	#[rpc(name = "sysMan_subtree")]
+	fn subtree(&self, id: AccountId, limit: u32, at: Option<BlockHash>) -> Result<Vec<AccountId>>;
+
+	/// Page of active or revoked organizations, starting after `start_after`.
+	#[rpc(name = "sysMan_orgs")]
+	fn orgs(
+		&self,
+		revoked: bool,
+		start_after: Option<AccountId>,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> Result<Vec<(AccountId, Account)>>;
+
+	/// Whether `id` is an active system manager that can exercise its authority.
+	#[rpc(name = "sysMan_isAuthority")]
+	fn is_authority(&self, id: AccountId, at: Option<BlockHash>) -> Result<bool>;
+
+	/// Page of up to `limit` audit log entries, oldest first, starting at `start`.
+	#[rpc(name = "sysMan_auditLog")]
+	fn audit_log(
+		&self,
+		start: AuditId,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> Result<Vec<(AuditId, AuditEntry)>>;
+
+	/// Page of up to `limit` audit log entries `account` took part in, oldest first,
+	/// skipping the first `start` of them.
+	#[rpc(name = "sysMan_auditLogOf")]
+	fn audit_log_of(
+		&self,
+		account: AccountId,
+		start: u32,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> Result<Vec<(AuditId, AuditEntry)>>;
+}
+
+/// Implementation of the sys-man RPC methods on top of the runtime API.
+pub struct SysMan<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> SysMan<C, B> {
+	/// Create a new `SysMan` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, AccountId, Account, AuditEntry>
+	SysManApi<<Block as BlockT>::Hash, AccountId, Account, AuditEntry> for SysMan<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: SysManRuntimeApi<Block, AccountId, Account, AuditEntry>,
+	AccountId: Codec,
+	Account: Codec,
+	AuditEntry: Codec,
+{
+	fn account(
+		&self,
+		id: AccountId,
+		op_type: OperationType,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<Account>> {
+		self.client
+			.runtime_api()
+			.account(&self.block_id(at), id, op_type)
+			.map_err(runtime_error)
+	}
+
+	fn revoked_account(
+		&self,
+		id: AccountId,
+		op_type: OperationType,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<Account>> {
+		self.client
+			.runtime_api()
+			.revoked_account(&self.block_id(at), id, op_type)
+			.map_err(runtime_error)
+	}
+
+	fn children(
+		&self,
+		id: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<AccountId>> {
+		self.client
+			.runtime_api()
+			.children(&self.block_id(at), id)
+			.map_err(runtime_error)
+	}
+
+	fn ancestors(
+		&self,
+		id: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<AccountId>> {
+		self.client
+			.runtime_api()
+			.ancestors(&self.block_id(at), id)
+			.map_err(runtime_error)
+	}
+
+	fn subtree(
+		&self,
+		id: AccountId,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<AccountId>> {
+		self.client
+			.runtime_api()
+			.subtree(&self.block_id(at), id, limit)
+			.map_err(runtime_error)
+	}
+
+	fn orgs(
+		&self,
+		revoked: bool,
+		start_after: Option<AccountId>,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<(AccountId, Account)>> {
+		self.client
+			.runtime_api()
+			.orgs(&self.block_id(at), revoked, start_after, limit)
+			.map_err(runtime_error)
+	}
+
+	fn is_authority(&self, id: AccountId, at: Option<<Block as BlockT>::Hash>) -> Result<bool> {
+		self.client
+			.runtime_api()
+			.is_authority(&self.block_id(at), id)
+			.map_err(runtime_error)
+	}
+
+	fn audit_log(
+		&self,
+		start: AuditId,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<(AuditId, AuditEntry)>> {
+		self.client
+			.runtime_api()
+			.audit_log(&self.block_id(at), start, limit)
+			.map_err(runtime_error)
+	}
+
+	fn audit_log_of(
+		&self,
+		account: AccountId,
+		start: u32,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<(AuditId, AuditEntry)>> {
+		self.client
+			.runtime_api()
+			.audit_log_of(&self.block_id(at), account, start, limit)
+			.map_err(runtime_error)
+	}
+}
+
+impl<C, Block> SysMan<C, Block>
+where
+	Block: BlockT,
+	C: HeaderBackend<Block>,
+{
+	/// Queries the given block, or the best block if none is given.
+	fn block_id(&self, at: Option<<Block as BlockT>::Hash>) -> BlockId<Block> {
+		BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash))
+	}
+}
+
+fn runtime_error(err: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(RUNTIME_ERROR),
+		message: "Unable to query the sys-man hierarchy.".into(),
+		data: Some(format!("{:?}", err).into()),
+	}
+}
@@ -11,20 +11,37 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod migrations;
+
 #[frame_support::pallet]
 pub mod pallet {
 
-	use codec::alloc::string::ToString;
-	use frame_support::{dispatch::DispatchResultWithPostInfo, ensure, pallet_prelude::*};
+	use codec::alloc::string::{String, ToString};
+	use frame_support::{
+		dispatch::DispatchResultWithPostInfo, ensure, pallet_prelude::*, traits::UnixTime,
+	};
 	use frame_system::pallet_prelude::*;
-	use pallet_utils::{Role, Status};
+	use pallet_utils::{
+		traits::{OrgMembership, SysManAuthority},
+		OrgRole, Role, Status,
+	};
 	use scale_info::TypeInfo;
 	use serde::{Deserialize, Serialize};
-	use serde_json::{json, Value};
-	use sp_std::{str, vec, vec::Vec};
+	use serde_json::{Map, Value};
+	use sp_runtime::traits::{Hash, Saturating};
+	use sp_std::{
+		collections::{BTreeSet, VecDeque},
+		str, vec,
+		vec::Vec,
+	};
 
 	// pub type String = Vec<u8>;
 
+	/// The current storage version.
+	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+	#[derive(Decode, Encode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 	pub enum OperationType {
 		SYS,
 		ORG,
@@ -33,6 +50,86 @@ pub mod pallet {
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Maximum number of descendants revoked by a single cascading revocation call.
+		#[pallet::constant]
+		type MaxCascadeRevocations: Get<u32>;
+
+		/// Maximum length in bytes of the JSON metadata of a system manager or organization.
+		#[pallet::constant]
+		type MaxMetadataLength: Get<u32>;
+
+		/// Maximum length in bytes of the description or reason given with a revocation or
+		/// reinstatement.
+		#[pallet::constant]
+		type MaxDescriptionLength: Get<u32>;
+
+		/// Default maximum level of a system manager, the root authority having level 0.
+		#[pallet::constant]
+		type MaxDepth: Get<u8>;
+
+		/// Default maximum number of system managers a single authority can appoint.
+		#[pallet::constant]
+		type MaxChildren: Get<u32>;
+
+		/// Highest fan-out `set_hierarchy_limits` can allow, bounding the children re-linked
+		/// when a system manager rotates its key.
+		#[pallet::constant]
+		type MaxChildrenLimit: Get<u32>;
+
+		/// Maximum number of organizations a single system manager can have approved at once.
+		#[pallet::constant]
+		type MaxOrgsPerSysMan: Get<u32>;
+
+		/// Default number of distinct system managers that have to endorse `approve_org`,
+		/// `revoke_org` and the approval of level-1 managers.
+		#[pallet::constant]
+		type ApprovalThreshold: Get<u32>;
+
+		/// Number of blocks after which a proposal that has not been executed is dropped.
+		#[pallet::constant]
+		type ProposalLifetime: Get<Self::BlockNumber>;
+
+		/// Maximum number of entries returned by a single page of a hierarchy or audit log query.
+		#[pallet::constant]
+		type MaxPageSize: Get<u32>;
+
+		/// Maximum number of staff accounts a single organization can register.
+		#[pallet::constant]
+		type MaxOrgMembers: Get<u32>;
+
+		/// Time source for the timestamps of the audit log.
+		type UnixTime: UnixTime;
+	}
+
+	/// JSON type a metadata field is expected to have.
+	#[derive(Decode, Encode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	pub enum JsonFieldType {
+		String,
+		Number,
+		Bool,
+		Object,
+		Array,
+	}
+
+	impl JsonFieldType {
+		pub fn matches(&self, value: &Value) -> bool {
+			match self {
+				JsonFieldType::String => value.is_string(),
+				JsonFieldType::Number => value.is_number(),
+				JsonFieldType::Bool => value.is_boolean(),
+				JsonFieldType::Object => value.is_object(),
+				JsonFieldType::Array => value.is_array(),
+			}
+		}
+	}
+
+	/// Fields every metadata object of a given operation type has to contain.
+	#[derive(Decode, Encode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo, Default)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	pub struct MetadataSchema {
+		pub required_fields: Vec<(Vec<u8>, JsonFieldType)>,
 	}
 
 	#[derive(Decode, Encode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
@@ -48,8 +145,91 @@ pub mod pallet {
 		pub metadata: Vec<u8>,
 	}
 
+	/// Progress of a cascading revocation that did not fit into a single call.
+	#[derive(Decode, Encode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	#[scale_info(bounds(), skip_type_params(T))]
+	pub struct CascadeRevocation<T: Config> {
+		pub revoker: T::AccountId,
+		pub description: Vec<u8>,
+		pub queue: Vec<T::AccountId>,
+	}
+
+	/// Who restored a revoked account, why and when.
+	#[derive(Decode, Encode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	#[scale_info(bounds(), skip_type_params(T))]
+	pub struct Reinstatement<T: Config> {
+		pub reinstated_by: T::AccountId,
+		pub reason: Vec<u8>,
+		pub block: T::BlockNumber,
+	}
+
+	pub type ProposalId = u32;
+
+	/// Operation which needs endorsements from several system managers.
+	#[derive(Decode, Encode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub enum ProposalAction<AccountId, BlockNumber> {
+		ApproveSysMan { sys_man_id: AccountId, metadata: Vec<u8>, expiry: Option<BlockNumber> },
+		ApproveOrg { org_id: AccountId, metadata: Vec<u8>, expiry: Option<BlockNumber> },
+		RevokeOrg { org_id: AccountId, description: Vec<u8> },
+		ReinstateSysMan { sys_man_id: AccountId, reason: Vec<u8> },
+		ReinstateOrg { org_id: AccountId, reason: Vec<u8> },
+	}
+
+	#[derive(Decode, Encode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	#[scale_info(bounds(), skip_type_params(T))]
+	pub struct Proposal<T: Config> {
+		pub proposer: T::AccountId,
+		pub action: ProposalAction<T::AccountId, T::BlockNumber>,
+		pub approvals: Vec<T::AccountId>,
+		pub expiry: T::BlockNumber,
+	}
+
+	pub type AuditId = u64;
+
+	/// Governance action recorded in the audit log.
+	#[derive(Decode, Encode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	pub enum AuditAction<AccountId> {
+		Approve(OperationType),
+		Revoke(OperationType),
+		Reinstate(OperationType),
+		Suspend(OperationType),
+		Resume(OperationType),
+		Renew(OperationType),
+		RotateKey(OperationType),
+		AddOrgMember { member: AccountId, role: OrgRole },
+		RemoveOrgMember { member: AccountId },
+		Propose(ProposalId),
+		Vote(ProposalId),
+		SetMetadataSchema(OperationType),
+		SetHierarchyLimits { max_depth: u8, max_children: u32 },
+		SetApprovalThreshold(u32),
+	}
+
+	/// Entry of the audit log. `actor` is `None` for actions of the root origin, and
+	/// `description_hash` is the hash of the metadata, description or reason given with the
+	/// action, if any.
+	#[derive(Decode, Encode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	pub struct AuditEntry<AccountId, BlockNumber, Hash> {
+		pub actor: Option<AccountId>,
+		pub action: AuditAction<AccountId>,
+		pub target: Option<AccountId>,
+		pub block: BlockNumber,
+		/// Milliseconds since the unix epoch.
+		pub timestamp: u64,
+		pub description_hash: Option<Hash>,
+	}
+
+	pub type AuditEntryOf<T> = AuditEntry<
+		<T as frame_system::Config>::AccountId,
+		<T as frame_system::Config>::BlockNumber,
+		<T as frame_system::Config>::Hash,
+	>;
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::storage]
@@ -72,6 +252,180 @@ pub mod pallet {
 	pub type OrgRevoked<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::AccountId, SysManAccount<T>, OptionQuery>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn sys_man_reinstated)]
+	/// Latest reinstatement of every system manager that has been restored after a revocation.
+	pub type SysManReinstated<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, Reinstatement<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn org_reinstated)]
+	/// Latest reinstatement of every organization that has been restored after a revocation.
+	pub type OrgReinstated<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, Reinstatement<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn suspended_until)]
+	/// Block at which a temporary suspension of a system manager or organization ends.
+	pub type SuspendedUntil<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Twox64Concat,
+		OperationType,
+		T::BlockNumber,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn suspension_expiries)]
+	/// Suspensions to be lifted at the beginning of a given block.
+	pub type SuspensionExpiries<T: Config> =
+		StorageMap<_, Twox64Concat, T::BlockNumber, Vec<(T::AccountId, OperationType)>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn metadata_schema)]
+	/// Schema against which the metadata of new system managers and organizations is validated.
+	pub type MetadataSchemas<T: Config> =
+		StorageMap<_, Twox64Concat, OperationType, MetadataSchema, ValueQuery>;
+
+	#[pallet::type_value]
+	pub fn DefaultMaxDepth<T: Config>() -> u8 {
+		T::MaxDepth::get()
+	}
+
+	#[pallet::type_value]
+	pub fn DefaultMaxChildren<T: Config>() -> u32 {
+		T::MaxChildren::get()
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn max_depth)]
+	/// Maximum level of a system manager, initialized from `Config::MaxDepth`.
+	pub type HierarchyMaxDepth<T: Config> = StorageValue<_, u8, ValueQuery, DefaultMaxDepth<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn max_children)]
+	/// Maximum number of children of a system manager, initialized from `Config::MaxChildren`.
+	pub type HierarchyMaxChildren<T: Config> =
+		StorageValue<_, u32, ValueQuery, DefaultMaxChildren<T>>;
+
+	#[pallet::type_value]
+	pub fn DefaultApprovalThreshold<T: Config>() -> u32 {
+		T::ApprovalThreshold::get()
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn required_approvals)]
+	/// Endorsements needed to execute a proposal, initialized from `Config::ApprovalThreshold`.
+	pub type RequiredApprovals<T: Config> =
+		StorageValue<_, u32, ValueQuery, DefaultApprovalThreshold<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn proposal_cnt)]
+	/// Id of the next proposal.
+	pub type ProposalCnt<T: Config> = StorageValue<_, ProposalId, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn proposal)]
+	pub type Proposals<T: Config> =
+		StorageMap<_, Twox64Concat, ProposalId, Proposal<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn proposal_expiries)]
+	/// Proposals to be dropped at the beginning of a given block.
+	pub type ProposalExpiries<T: Config> =
+		StorageMap<_, Twox64Concat, T::BlockNumber, Vec<ProposalId>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn mandate_expiry)]
+	/// Block at which the mandate of a system manager or organization ends.
+	pub type MandateExpiry<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Twox64Concat,
+		OperationType,
+		T::BlockNumber,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn mandate_expiries)]
+	/// Mandates ending at a given block.
+	pub type MandateExpiries<T: Config> =
+		StorageMap<_, Twox64Concat, T::BlockNumber, Vec<(T::AccountId, OperationType)>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn rotated_to)]
+	/// Key a system manager or organization moved to when rotating away from a given key.
+	pub type RotatedTo<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Twox64Concat,
+		OperationType,
+		T::AccountId,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn rotated_from)]
+	/// Key a system manager or organization used before rotating to a given key.
+	pub type RotatedFrom<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Twox64Concat,
+		OperationType,
+		T::AccountId,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn orgs_by_sys_man)]
+	/// Organizations approved by a system manager.
+	pub type OrgsBySysMan<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AccountId,
+		(),
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn org_cnt_by_sys_man)]
+	/// Number of organizations approved by a system manager.
+	pub type OrgCntBySysMan<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn org_member)]
+	/// Role of the staff accounts registered by an organization.
+	pub type OrgMembers<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AccountId,
+		OrgRole,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn org_member_cnt)]
+	/// Number of staff accounts registered by an organization.
+	pub type OrgMemberCnt<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn pending_cascade)]
+	/// Unfinished cascading revocations, keyed by the root of the revoked subtree.
+	pub type PendingCascade<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, CascadeRevocation<T>, OptionQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn sys_man_cnt)]
 	/// Keeps track of the number of system managers in existence.
@@ -79,18 +433,49 @@ pub mod pallet {
 
 	#[pallet::storage]
 	#[pallet::getter(fn org_cnt)]
-	/// Keeps track of the number of system managers in existence.
+	/// Keeps track of the number of organizations in existence.
 	pub(super) type OrgCnt<T: Config> = StorageValue<_, u64, ValueQuery>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn audit_log_cnt)]
+	/// Id of the next audit log entry.
+	pub type AuditLogCnt<T: Config> = StorageValue<_, AuditId, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn audit_log)]
+	/// Append-only log of every governance action.
+	pub type AuditLog<T: Config> =
+		StorageMap<_, Twox64Concat, AuditId, AuditEntryOf<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn audit_log_by_account_cnt)]
+	/// Number of audit log entries an account took part in, as actor or target.
+	pub type AuditLogByAccountCnt<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn audit_log_by_account)]
+	/// Audit log entries an account took part in, in the order they were recorded.
+	pub type AuditLogByAccount<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Twox64Concat,
+		u32,
+		AuditId,
+		OptionQuery,
+	>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub sys_man: Vec<(T::AccountId, SysManAccount<T>)>,
+		pub metadata_schemas: Vec<(OperationType, MetadataSchema)>,
 	}
 
 	#[cfg(feature = "std")]
 	impl<T: Config> Default for GenesisConfig<T> {
 		fn default() -> GenesisConfig<T> {
-			Self { sys_man: Default::default() }
+			Self { sys_man: Default::default(), metadata_schemas: Default::default() }
 		}
 	}
 
@@ -100,14 +485,100 @@ pub mod pallet {
 			for (account_id, sys_man_account) in &self.sys_man {
 				SysMan::<T>::insert(account_id, sys_man_account);
 			}
+
+			SysManCnt::<T>::put(self.sys_man.len() as u64);
+
+			// make sure every configured parent knows about its children
+			for (account_id, sys_man_account) in &self.sys_man {
+				if let Some(parent) = &sys_man_account.parent {
+					Pallet::<T>::add_child(parent, account_id);
+				}
+			}
+
+			for (op_type, schema) in &self.metadata_schemas {
+				MetadataSchemas::<T>::insert(op_type, schema);
+			}
 		}
 	}
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
-		Approved { target_id: T::AccountId, metadata: Vec<u8>, approver: T::AccountId },
-		Revoked { target_id: T::AccountId, revoker: T::AccountId },
+		Approved {
+			target_id: T::AccountId,
+			metadata: Vec<u8>,
+			approver: T::AccountId,
+		},
+		Revoked {
+			target_id: T::AccountId,
+			revoker: T::AccountId,
+		},
+		CascadeRevocationPending {
+			root_id: T::AccountId,
+			remaining: u32,
+		},
+		CascadeRevocationCompleted {
+			root_id: T::AccountId,
+		},
+		Reinstated {
+			target_id: T::AccountId,
+			reinstater: T::AccountId,
+			reason: Vec<u8>,
+		},
+		Suspended {
+			target_id: T::AccountId,
+			suspender: T::AccountId,
+			until: Option<T::BlockNumber>,
+		},
+		Resumed {
+			target_id: T::AccountId,
+		},
+		HierarchyLimitsUpdated {
+			max_depth: u8,
+			max_children: u32,
+		},
+		ApprovalThresholdUpdated {
+			threshold: u32,
+		},
+		Proposed {
+			proposal_id: ProposalId,
+			proposer: T::AccountId,
+			action: ProposalAction<T::AccountId, T::BlockNumber>,
+		},
+		Voted {
+			proposal_id: ProposalId,
+			voter: T::AccountId,
+			approvals: u32,
+		},
+		ProposalExecuted {
+			proposal_id: ProposalId,
+		},
+		ProposalExpired {
+			proposal_id: ProposalId,
+		},
+		MandateExpired {
+			target_id: T::AccountId,
+			op_type: OperationType,
+		},
+		MandateRenewed {
+			target_id: T::AccountId,
+			op_type: OperationType,
+			expiry: Option<T::BlockNumber>,
+		},
+		KeyRotated {
+			old_id: T::AccountId,
+			new_id: T::AccountId,
+			op_type: OperationType,
+		},
+		OrgMemberAdded {
+			org_id: T::AccountId,
+			member: T::AccountId,
+			role: OrgRole,
+		},
+		OrgMemberRemoved {
+			org_id: T::AccountId,
+			member: T::AccountId,
+		},
 	}
 
 	#[pallet::error]
@@ -123,6 +594,52 @@ pub mod pallet {
 		RevokedSysManNotExist,
 		RevokedOrgNotExist,
 		OperationTypeInvalid,
+		CascadeNotPending,
+		AccountSuspended,
+		AlreadySuspended,
+		NotSuspended,
+		InvalidSuspensionPeriod,
+		MetadataTooLong,
+		MetadataNotJsonObject,
+		MetadataMissingField,
+		MetadataInvalidFieldType,
+		InvalidMetadataSchema,
+		DescriptionNotUtf8,
+		DescriptionTooLong,
+		MissingLevel,
+		LevelOverflow,
+		MaxDepthExceeded,
+		TooManyChildren,
+		HierarchyLimitTooHigh,
+		TooManyOrgs,
+		ProposalRequired,
+		ProposalNotExist,
+		ProposalExpired,
+		AlreadyVoted,
+		InsufficientApprovals,
+		ProposalIdOverflow,
+		MandateExpired,
+		InvalidExpiry,
+		KeyAlreadyUsed,
+		NotOrgAdmin,
+		NotOrgMember,
+		TooManyMembers,
+		NotOrgApprover,
+		NotEligibleEndorser,
+		ConflictingEndorsement,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			crate::migrations::v1::migrate::<T>()
+		}
+
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			Self::lift_suspensions(now)
+				.saturating_add(Self::expire_proposals(now))
+				.saturating_add(Self::expire_mandates(now))
+		}
 	}
 
 	#[pallet::call]
@@ -132,36 +649,20 @@ pub mod pallet {
 			origin: OriginFor<T>,
 			sys_man_id: T::AccountId,
 			metadata: Vec<u8>,
+			expiry: Option<T::BlockNumber>,
 		) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
 
-			// ensure extrinsics caller has right permission
-			let authority = Self::get_account(&sender, OperationType::SYS)?;
-
-			// check whether sys man has been approved
-			ensure!(!SysMan::<T>::contains_key(&sys_man_id), Error::<T>::AlreadyRegistered);
-
-			// check whether sys man has been revoked
-			ensure!(!SysManRevoked::<T>::contains_key(&sys_man_id), Error::<T>::AlreadyRevoked);
-
-			// create system manager account
-			let sys_man = Self::create_account(
-				Role::SysMan,
-				Status::Active,
-				Some(authority.level.unwrap() + 1),
-				Some(vec![]),
-				Some(sender.clone()),
-				metadata.clone(),
-			)
-			.unwrap();
+			// level-1 managers can only be approved through a proposal once endorsements are
+			// required
+			let authority = Self::get_authority(&sender)?;
 
-			SysMan::<T>::insert(&sys_man_id, sys_man);
+			ensure!(
+				Self::child_level(&authority)? != 1 || Self::required_approvals() <= 1,
+				Error::<T>::ProposalRequired
+			);
 
-			Self::deposit_event(Event::<T>::Approved {
-				target_id: sys_man_id,
-				metadata,
-				approver: sender,
-			});
+			Self::do_approve_sys_man(sender, sys_man_id, metadata, expiry)?;
 
 			Ok(().into())
 		}
@@ -171,146 +672,630 @@ pub mod pallet {
 			origin: OriginFor<T>,
 			org_id: T::AccountId,
 			metadata: Vec<u8>,
+			expiry: Option<T::BlockNumber>,
 		) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
 
-			// check permisisno of authority
-			let _ = Self::get_account(&sender, OperationType::SYS)?;
+			ensure!(Self::required_approvals() <= 1, Error::<T>::ProposalRequired);
 
-			// check whether org has been approved
-			ensure!(!Org::<T>::contains_key(&org_id), Error::<T>::AlreadyRegistered);
+			Self::do_approve_org(sender, org_id, metadata, expiry)?;
 
-			// check whether org has been revoked
-			ensure!(!OrgRevoked::<T>::contains_key(&org_id), Error::<T>::AlreadyRevoked);
+			Ok(().into())
+		}
 
-			// create system manager account
-			let org_account = Self::create_account(
-				Role::Organization,
-				Status::Active,
-				None,
-				None,
-				None,
-				metadata.clone(),
-			)
-			.unwrap();
+		#[pallet::weight(10_000)]
+		pub fn revoke_org(
+			origin: OriginFor<T>,
+			revoke_org_id: T::AccountId,
+			description: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
 
-			Org::<T>::insert(&org_id, org_account);
+			ensure!(Self::required_approvals() <= 1, Error::<T>::ProposalRequired);
 
-			Self::deposit_event(Event::<T>::Approved {
-				target_id: org_id,
-				metadata,
-				approver: sender,
-			});
+			Self::do_revoke_org(sender, revoke_org_id, description)?;
 
 			Ok(().into())
 		}
 
 		#[pallet::weight(10_000)]
-		pub fn revoke_org(
+		pub fn revoke_sys_man(
 			origin: OriginFor<T>,
-			revoke_org_id: T::AccountId,
+			revoke_id: T::AccountId,
 			description: Vec<u8>,
 		) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
 
-			// check permission of revoker
-			let _ = Self::get_account(&sender, OperationType::SYS)?;
+			let revoke_sys_man = Self::ensure_can_revoke_sys_man(&sender, &revoke_id)?;
 
-			let mut revoke_org = Self::get_account(&revoke_org_id, OperationType::ORG)?;
+			Self::do_revoke_sys_man(&sender, &revoke_id, revoke_sys_man, &description)?;
 
-			// ensure revoked org has not been revoked yet
-			ensure!(!OrgRevoked::<T>::contains_key(&revoke_org_id), Error::<T>::AlreadyRevoked);
+			// emit revoked event with information of revoked sys man
+			Self::deposit_event(Event::<T>::Revoked { target_id: revoke_id, revoker: sender });
 
-			// add revoked description to metadata string
-			let mut metadata = json!(revoke_org.metadata);
+			Ok(().into())
+		}
 
-			metadata = Self::add_json_field(
-				&metadata,
-				"revoke_description",
-				&str::from_utf8(&description).unwrap(),
-			);
+		/// Revoke a system manager together with every manager below it in the hierarchy.
+		///
+		/// At most `MaxCascadeRevocations` descendants are revoked in this call, the rest of the
+		/// subtree can be revoked with `continue_cascade_revocation`. Every revocation takes 7
+		/// reads and 13 writes, audit log entry included.
+		#[pallet::weight(
+			10_000 + T::DbWeight::get().reads_writes(7, 13)
+				.saturating_mul(T::MaxCascadeRevocations::get() as Weight + 1)
+		)]
+		pub fn revoke_sys_man_cascade(
+			origin: OriginFor<T>,
+			revoke_id: T::AccountId,
+			description: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
 
-			revoke_org.metadata = metadata.to_string().as_bytes().to_vec();
+			let revoke_sys_man = Self::ensure_can_revoke_sys_man(&sender, &revoke_id)?;
 
-			// remove revoked sys man from Org Storage
-			Org::<T>::remove(&revoke_org_id);
+			let queue = revoke_sys_man.children.clone().unwrap_or_default();
 
-			// add revoked sys man to OrgRevoked Storage
-			OrgRevoked::<T>::insert(&revoke_org_id, revoke_org);
+			Self::do_revoke_sys_man(&sender, &revoke_id, revoke_sys_man, &description)?;
 
-			// emit revoked event with information of revoked sys man
-			Self::deposit_event(Event::<T>::Revoked { target_id: revoke_org_id, revoker: sender });
+			Self::deposit_event(Event::<T>::Revoked {
+				target_id: revoke_id.clone(),
+				revoker: sender.clone(),
+			});
+
+			Self::process_cascade(
+				revoke_id,
+				CascadeRevocation::<T> { revoker: sender, description, queue },
+			)?;
+
+			Ok(().into())
+		}
+
+		/// Continue a cascading revocation which could not be finished in a single call.
+		///
+		/// The revocation has already been authorized, so any signed account may push it forward.
+		#[pallet::weight(
+			10_000 + T::DbWeight::get().reads_writes(7, 13)
+				.saturating_mul(T::MaxCascadeRevocations::get() as Weight + 1)
+		)]
+		pub fn continue_cascade_revocation(
+			origin: OriginFor<T>,
+			root_id: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			let _ = ensure_signed(origin)?;
+
+			let cascade =
+				PendingCascade::<T>::take(&root_id).ok_or(Error::<T>::CascadeNotPending)?;
+
+			Self::process_cascade(root_id, cascade)?;
 
 			Ok(().into())
 		}
 
 		#[pallet::weight(10_000)]
-		pub fn revoke_sys_man(
+		pub fn reinstate_sys_man(
 			origin: OriginFor<T>,
-			revoke_id: T::AccountId,
-			description: Vec<u8>,
+			reinstate_id: T::AccountId,
+			reason: Vec<u8>,
 		) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
 
-			let authority = Self::get_account(&sender, OperationType::SYS)?;
+			// managers coming back at level 1 can only be reinstated through a proposal once
+			// endorsements are required, just like they are approved
+			let authority = Self::get_authority(&sender)?;
 
-			let mut revoke_sys_man = Self::get_account(&revoke_id, OperationType::SYS)?;
+			let reinstate_sys_man =
+				SysManRevoked::<T>::get(&reinstate_id).ok_or(Error::<T>::RevokedSysManNotExist)?;
 
-			// Ensure authority has higher hierarchical level than system manager to be revoked
 			ensure!(
-				authority.level.unwrap_or(0) < revoke_sys_man.level.unwrap_or(0),
-				Error::<T>::NoValidAuthorization
+				Self::reinstated_level(&authority, &reinstate_sys_man)? != 1 ||
+					Self::required_approvals() <= 1,
+				Error::<T>::ProposalRequired
 			);
 
-			// ensure revoked sys man has not been revoked yet
-			ensure!(!SysManRevoked::<T>::contains_key(&revoke_id), Error::<T>::AlreadyRevoked);
+			Self::do_reinstate_sys_man(sender, reinstate_id, reason)?;
 
-			// add revoked description to metadata string
+			Ok(().into())
+		}
 
-			let mut metadata = json!(revoke_sys_man.metadata);
+		#[pallet::weight(10_000)]
+		pub fn reinstate_org(
+			origin: OriginFor<T>,
+			reinstate_org_id: T::AccountId,
+			reason: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
 
-			metadata = Self::add_json_field(
-				&metadata,
-				"revoke_description",
-				&str::from_utf8(&description).unwrap(),
-			);
+			ensure!(Self::required_approvals() <= 1, Error::<T>::ProposalRequired);
 
-			// metadata["revoke_description"] = Value::String(String::from_utf8(description).unwrap());
+			Self::do_reinstate_org(sender, reinstate_org_id, reason)?;
 
-			revoke_sys_man.metadata = metadata.to_string().as_bytes().to_vec();
+			Ok(().into())
+		}
 
-			// remove revoked sys man from SysMan Storage
-			SysMan::<T>::remove(&revoke_id);
+		/// Temporarily suspend a system manager, optionally until the given block.
+		#[pallet::weight(10_000)]
+		pub fn suspend_sys_man(
+			origin: OriginFor<T>,
+			suspend_id: T::AccountId,
+			until: Option<T::BlockNumber>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
 
-			// add revoked sys man to SysManRevoked Storage
-			SysManRevoked::<T>::insert(&revoke_id, revoke_sys_man);
+			let authority = Self::get_authority(&sender)?;
 
-			// emit revoked event with information of revoked sys man
-			Self::deposit_event(Event::<T>::Revoked { target_id: revoke_id, revoker: sender });
+			let suspend_sys_man = Self::get_account(&suspend_id, OperationType::SYS)?;
+
+			// Ensure authority has higher hierarchical level than system manager to be suspended
+			Self::ensure_outranks(&authority, &suspend_sys_man)?;
+
+			Self::do_suspend(sender, suspend_id, suspend_sys_man, OperationType::SYS, until)?;
 
 			Ok(().into())
 		}
-	}
 
-	// private functions
-	impl<T: Config> Pallet<T> {
-		pub fn create_account(
-			role: Role,
-			status: Status,
-			level: Option<u8>,
-			children: Option<Vec<T::AccountId>>,
-			parent: Option<T::AccountId>,
-			metadata: Vec<u8>,
-		) -> Result<SysManAccount<T>, Error<T>> {
-			// TODO: validate metadata to be a valid JSON string
+		#[pallet::weight(10_000)]
+		pub fn resume_sys_man(
+			origin: OriginFor<T>,
+			resume_id: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
 
-			let sys_man = SysManAccount::<T> { role, status, level, children, parent, metadata };
+			let authority = Self::get_authority(&sender)?;
 
-			Ok(sys_man)
+			let resume_sys_man = Self::get_account(&resume_id, OperationType::SYS)?;
+
+			// Ensure authority has higher hierarchical level than system manager to be resumed
+			Self::ensure_outranks(&authority, &resume_sys_man)?;
+
+			Self::do_resume(sender, resume_id, resume_sys_man, OperationType::SYS)?;
+
+			Ok(().into())
 		}
 
-		pub fn get_account(
-			id: &T::AccountId,
+		/// Temporarily suspend an organization, optionally until the given block.
+		#[pallet::weight(10_000)]
+		pub fn suspend_org(
+			origin: OriginFor<T>,
+			suspend_org_id: T::AccountId,
+			until: Option<T::BlockNumber>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let authority = Self::get_authority(&sender)?;
+
+			let suspend_org = Self::get_account(&suspend_org_id, OperationType::ORG)?;
+
+			Self::ensure_org_approver(&sender, &authority, &suspend_org)?;
+
+			Self::do_suspend(sender, suspend_org_id, suspend_org, OperationType::ORG, until)?;
+
+			Ok(().into())
+		}
+
+		#[pallet::weight(10_000)]
+		pub fn resume_org(
+			origin: OriginFor<T>,
+			resume_org_id: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let authority = Self::get_authority(&sender)?;
+
+			let resume_org = Self::get_account(&resume_org_id, OperationType::ORG)?;
+
+			Self::ensure_org_approver(&sender, &authority, &resume_org)?;
+
+			Self::do_resume(sender, resume_org_id, resume_org, OperationType::ORG)?;
+
+			Ok(().into())
+		}
+
+		/// Replace the metadata schema of system managers or organizations.
+		#[pallet::weight(10_000)]
+		pub fn set_metadata_schema(
+			origin: OriginFor<T>,
+			op_type: OperationType,
+			schema: MetadataSchema,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			for (key, _) in schema.required_fields.iter() {
+				ensure!(str::from_utf8(key).is_ok(), Error::<T>::InvalidMetadataSchema);
+			}
+
+			MetadataSchemas::<T>::insert(op_type, schema);
+
+			Self::record_audit(None, AuditAction::SetMetadataSchema(op_type), None, None);
+
+			Ok(().into())
+		}
+
+		/// Change the maximum depth and fan-out of the system manager hierarchy.
+		///
+		/// Existing managers beyond the new limits are kept, the limits only apply to new
+		/// appointments. The fan-out can't go above `MaxChildrenLimit`.
+		#[pallet::weight(10_000)]
+		pub fn set_hierarchy_limits(
+			origin: OriginFor<T>,
+			max_depth: u8,
+			max_children: u32,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			ensure!(
+				max_children <= T::MaxChildrenLimit::get(),
+				Error::<T>::HierarchyLimitTooHigh
+			);
+
+			HierarchyMaxDepth::<T>::put(max_depth);
+			HierarchyMaxChildren::<T>::put(max_children);
+
+			Self::record_audit(
+				None,
+				AuditAction::SetHierarchyLimits { max_depth, max_children },
+				None,
+				None,
+			);
+
+			Self::deposit_event(Event::<T>::HierarchyLimitsUpdated { max_depth, max_children });
+
+			Ok(().into())
+		}
+
+		/// Change the number of endorsements needed to execute a proposal.
+		#[pallet::weight(10_000)]
+		pub fn set_approval_threshold(
+			origin: OriginFor<T>,
+			threshold: u32,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			RequiredApprovals::<T>::put(threshold);
+
+			Self::record_audit(None, AuditAction::SetApprovalThreshold(threshold), None, None);
+
+			Self::deposit_event(Event::<T>::ApprovalThresholdUpdated { threshold });
+
+			Ok(().into())
+		}
+
+		/// Propose an operation that needs endorsements. The proposer endorses it implicitly.
+		#[pallet::weight(10_000)]
+		pub fn propose(
+			origin: OriginFor<T>,
+			action: ProposalAction<T::AccountId, T::BlockNumber>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			Self::ensure_can_endorse(&sender)?;
+
+			Self::ensure_valid_action(&action)?;
+
+			let proposal_id = Self::proposal_cnt();
+			let next_id = proposal_id.checked_add(1).ok_or(Error::<T>::ProposalIdOverflow)?;
+
+			let expiry = frame_system::Pallet::<T>::block_number()
+				.saturating_add(T::ProposalLifetime::get());
+
+			Proposals::<T>::insert(
+				proposal_id,
+				Proposal::<T> {
+					proposer: sender.clone(),
+					action: action.clone(),
+					approvals: vec![sender.clone()],
+					expiry,
+				},
+			);
+			ProposalExpiries::<T>::append(expiry, proposal_id);
+			ProposalCnt::<T>::put(next_id);
+
+			Self::record_audit(Some(sender.clone()), AuditAction::Propose(proposal_id), None, None);
+
+			Self::deposit_event(Event::<T>::Proposed { proposal_id, proposer: sender, action });
+
+			Ok(().into())
+		}
+
+		#[pallet::weight(10_000)]
+		pub fn vote(origin: OriginFor<T>, proposal_id: ProposalId) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			Self::ensure_can_endorse(&sender)?;
+
+			let mut proposal = Self::get_open_proposal(proposal_id)?;
+
+			let endorsers = Self::current_endorsers(&proposal);
+
+			ensure!(!endorsers.contains(&sender), Error::<T>::AlreadyVoted);
+
+			// a manager can not second a proposal of the manager which appointed it
+			ensure!(
+				!Self::ancestors(&sender).iter().any(|ancestor| endorsers.contains(ancestor)),
+				Error::<T>::ConflictingEndorsement
+			);
+
+			proposal.approvals.push(sender.clone());
+
+			let approvals = proposal.approvals.len() as u32;
+
+			Proposals::<T>::insert(proposal_id, proposal);
+
+			Self::record_audit(Some(sender.clone()), AuditAction::Vote(proposal_id), None, None);
+
+			Self::deposit_event(Event::<T>::Voted { proposal_id, voter: sender, approvals });
+
+			Ok(().into())
+		}
+
+		/// Execute a proposal once enough system managers have endorsed it.
+		#[pallet::weight(10_000)]
+		pub fn execute_proposal(
+			origin: OriginFor<T>,
+			proposal_id: ProposalId,
+		) -> DispatchResultWithPostInfo {
+			let _ = ensure_signed(origin)?;
+
+			let proposal = Self::get_open_proposal(proposal_id)?;
+
+			let approvals = Self::count_endorsements(&proposal);
+
+			ensure!(approvals >= Self::required_approvals(), Error::<T>::InsufficientApprovals);
+
+			// the proposer may have rotated its key since
+			let proposer = Self::current_key(&proposal.proposer, OperationType::SYS);
+
+			Self::execute_action(proposer, proposal.action)?;
+
+			Proposals::<T>::remove(proposal_id);
+
+			Self::deposit_event(Event::<T>::ProposalExecuted { proposal_id });
+
+			Ok(().into())
+		}
+
+		/// Renew the mandate of a system manager until the given block, or indefinitely.
+		#[pallet::weight(10_000)]
+		pub fn renew_sys_man(
+			origin: OriginFor<T>,
+			renew_id: T::AccountId,
+			expiry: Option<T::BlockNumber>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let authority = Self::get_authority(&sender)?;
+
+			let renew_sys_man = Self::get_account(&renew_id, OperationType::SYS)?;
+
+			// Ensure authority has higher hierarchical level than system manager to be renewed
+			Self::ensure_outranks(&authority, &renew_sys_man)?;
+
+			Self::do_renew(sender, renew_id, OperationType::SYS, expiry)?;
+
+			Ok(().into())
+		}
+
+		/// Renew the mandate of an organization until the given block, or indefinitely.
+		#[pallet::weight(10_000)]
+		pub fn renew_org(
+			origin: OriginFor<T>,
+			renew_org_id: T::AccountId,
+			expiry: Option<T::BlockNumber>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let authority = Self::get_authority(&sender)?;
+
+			let renew_org = Self::get_account(&renew_org_id, OperationType::ORG)?;
+
+			Self::ensure_org_approver(&sender, &authority, &renew_org)?;
+
+			Self::do_renew(sender, renew_org_id, OperationType::ORG, expiry)?;
+
+			Ok(().into())
+		}
+
+		/// Move the system manager record of the signer to a new key.
+		///
+		/// The approver, the managers appointed by the signer and the organizations it approved
+		/// are re-linked to the new key. Its proposals and endorsements follow the new key
+		/// through `RotatedTo`.
+		#[pallet::weight(Pallet::<T>::rotate_sys_man_weight(
+			T::MaxChildrenLimit::get(),
+			T::MaxOrgsPerSysMan::get()
+		))]
+		pub fn rotate_sys_man_key(
+			origin: OriginFor<T>,
+			new_id: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let sys_man = Self::get_account(&sender, OperationType::SYS)?;
+
+			ensure!(!SysMan::<T>::contains_key(&new_id), Error::<T>::AlreadyRegistered);
+			ensure!(!SysManRevoked::<T>::contains_key(&new_id), Error::<T>::AlreadyRevoked);
+			Self::ensure_unused_key(&new_id, OperationType::SYS)?;
+
+			// point the approver to the new key, keeping the order of its children
+			if let Some(parent) = &sys_man.parent {
+				SysMan::<T>::mutate(parent, |maybe_parent| {
+					if let Some(children) = maybe_parent.as_mut().and_then(|p| p.children.as_mut())
+					{
+						children
+							.iter_mut()
+							.filter(|c| **c == sender)
+							.for_each(|c| *c = new_id.clone());
+					}
+				});
+			}
+
+			let children = sys_man.children.clone().unwrap_or_default();
+
+			// point appointed managers to the new key, revoked ones reach it through `RotatedTo`
+			for child in children.iter() {
+				SysMan::<T>::mutate(child, |maybe_child| {
+					if let Some(child) = maybe_child {
+						if child.parent.as_ref() == Some(&sender) {
+							child.parent = Some(new_id.clone());
+						}
+					}
+				});
+			}
+
+			// point approved organizations to the new key
+			let orgs = OrgCntBySysMan::<T>::take(&sender);
+
+			for (org_id, _) in OrgsBySysMan::<T>::drain_prefix(&sender) {
+				Org::<T>::mutate(&org_id, |maybe_org| {
+					if let Some(org) = maybe_org {
+						org.parent = Some(new_id.clone());
+					}
+				});
+
+				OrgsBySysMan::<T>::insert(&new_id, &org_id, ());
+			}
+
+			OrgCntBySysMan::<T>::insert(&new_id, orgs);
+
+			SysMan::<T>::remove(&sender);
+			SysMan::<T>::insert(&new_id, sys_man);
+
+			Self::move_key(&sender, &new_id, OperationType::SYS);
+
+			Ok(Some(Self::rotate_sys_man_weight(children.len() as u32, orgs)).into())
+		}
+
+		/// Move the organization record of the signer to a new key.
+		///
+		/// The registered staff accounts are moved along, so the weight grows with
+		/// `MaxOrgMembers`.
+		#[pallet::weight(Pallet::<T>::rotate_org_weight(T::MaxOrgMembers::get()))]
+		pub fn rotate_org_key(
+			origin: OriginFor<T>,
+			new_id: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let org = Self::get_account(&sender, OperationType::ORG)?;
+
+			ensure!(!Org::<T>::contains_key(&new_id), Error::<T>::AlreadyRegistered);
+			ensure!(!OrgRevoked::<T>::contains_key(&new_id), Error::<T>::AlreadyRevoked);
+			Self::ensure_unused_key(&new_id, OperationType::ORG)?;
+
+			if let Some(parent) = &org.parent {
+				OrgsBySysMan::<T>::remove(parent, &sender);
+				OrgsBySysMan::<T>::insert(parent, &new_id, ());
+			}
+
+			Org::<T>::remove(&sender);
+			Org::<T>::insert(&new_id, org);
+
+			for (member, role) in OrgMembers::<T>::drain_prefix(&sender) {
+				OrgMembers::<T>::insert(&new_id, member, role);
+			}
+			let members = OrgMemberCnt::<T>::take(&sender);
+
+			OrgMemberCnt::<T>::insert(&new_id, members);
+
+			Self::move_key(&sender, &new_id, OperationType::ORG);
+
+			Ok(Some(Self::rotate_org_weight(members)).into())
+		}
+
+		/// Register a staff account acting on behalf of an organization, or change its role.
+		///
+		/// Can be called by the organization key or one of its admins.
+		#[pallet::weight(10_000)]
+		pub fn add_org_member(
+			origin: OriginFor<T>,
+			org_id: T::AccountId,
+			member: T::AccountId,
+			role: OrgRole,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			Self::ensure_org_admin(&org_id, &sender)?;
+
+			if !OrgMembers::<T>::contains_key(&org_id, &member) {
+				let cnt = OrgMemberCnt::<T>::get(&org_id);
+
+				ensure!(cnt < T::MaxOrgMembers::get(), Error::<T>::TooManyMembers);
+
+				OrgMemberCnt::<T>::insert(&org_id, cnt + 1);
+			}
+
+			OrgMembers::<T>::insert(&org_id, &member, role);
+
+			Self::record_audit(
+				Some(sender),
+				AuditAction::AddOrgMember { member: member.clone(), role },
+				Some(org_id.clone()),
+				None,
+			);
+
+			Self::deposit_event(Event::<T>::OrgMemberAdded { org_id, member, role });
+
+			Ok(().into())
+		}
+
+		/// Remove a staff account from an organization.
+		///
+		/// Can be called by the organization key, one of its admins or the member itself.
+		#[pallet::weight(10_000)]
+		pub fn remove_org_member(
+			origin: OriginFor<T>,
+			org_id: T::AccountId,
+			member: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(OrgMembers::<T>::contains_key(&org_id, &member), Error::<T>::NotOrgMember);
+
+			if sender != member {
+				Self::ensure_org_admin(&org_id, &sender)?;
+			}
+
+			OrgMembers::<T>::remove(&org_id, &member);
+			OrgMemberCnt::<T>::mutate(&org_id, |cnt| *cnt = cnt.saturating_sub(1));
+
+			Self::record_audit(
+				Some(sender),
+				AuditAction::RemoveOrgMember { member: member.clone() },
+				Some(org_id.clone()),
+				None,
+			);
+
+			Self::deposit_event(Event::<T>::OrgMemberRemoved { org_id, member });
+
+			Ok(().into())
+		}
+	}
+
+	// private functions
+	impl<T: Config> Pallet<T> {
+		pub fn create_account(
+			role: Role,
+			status: Status,
+			level: Option<u8>,
+			children: Option<Vec<T::AccountId>>,
+			parent: Option<T::AccountId>,
+			metadata: Vec<u8>,
+		) -> Result<SysManAccount<T>, Error<T>> {
+			let op_type = match role {
+				Role::SysMan => OperationType::SYS,
+				Role::Organization => OperationType::ORG,
+				Role::User => Err(Error::<T>::OperationTypeInvalid)?,
+			};
+
+			Self::validate_metadata(&metadata, op_type)?;
+
+			let sys_man = SysManAccount::<T> { role, status, level, children, parent, metadata };
+
+			Ok(sys_man)
+		}
+
+		pub fn get_account(
+			id: &T::AccountId,
 			op_type: OperationType,
 		) -> Result<SysManAccount<T>, Error<T>> {
 			let authority = match op_type {
@@ -334,21 +1319,1197 @@ pub mod pallet {
 			Ok(authority)
 		}
 
-		pub fn str2vec(s: &str) -> Vec<u8> {
-			s.as_bytes().to_vec()
-		}
+		fn do_approve_sys_man(
+			sender: T::AccountId,
+			sys_man_id: T::AccountId,
+			metadata: Vec<u8>,
+			expiry: Option<T::BlockNumber>,
+		) -> Result<(), Error<T>> {
+			// ensure extrinsics caller has right permission
+			let authority = Self::get_authority(&sender)?;
 
-		fn add_json_field(v: &Value, field_key: &str, field_val: &str) -> Value {
-			match v {
-				Value::Object(map) => {
-					let mut map = map.clone();
+			// check whether sys man has been approved
+			ensure!(!SysMan::<T>::contains_key(&sys_man_id), Error::<T>::AlreadyRegistered);
 
-					map.insert(field_key.to_string(), Value::String(field_val.to_string()));
+			// check whether sys man has been revoked
+			ensure!(!SysManRevoked::<T>::contains_key(&sys_man_id), Error::<T>::AlreadyRevoked);
 
-					Value::Object(map)
-				},
-				v => v.clone(),
-			}
+			// ensure the hierarchy stays within the configured depth and fan-out
+			let level = Self::ensure_can_appoint(&authority)?;
+
+			Self::ensure_valid_expiry(expiry)?;
+
+			// create system manager account
+			let sys_man = Self::create_account(
+				Role::SysMan,
+				Status::Active,
+				Some(level),
+				Some(vec![]),
+				Some(sender.clone()),
+				metadata.clone(),
+			)?;
+
+			SysMan::<T>::insert(&sys_man_id, sys_man);
+
+			Self::set_mandate_expiry(&sys_man_id, OperationType::SYS, expiry);
+
+			// register new sys man as a child of its approver
+			Self::add_child(&sender, &sys_man_id);
+
+			SysManCnt::<T>::mutate(|cnt| *cnt = cnt.saturating_add(1));
+
+			Self::record_audit(
+				Some(sender.clone()),
+				AuditAction::Approve(OperationType::SYS),
+				Some(sys_man_id.clone()),
+				Some(&metadata),
+			);
+
+			Self::deposit_event(Event::<T>::Approved {
+				target_id: sys_man_id,
+				metadata,
+				approver: sender,
+			});
+
+			Ok(())
+		}
+
+		fn do_approve_org(
+			sender: T::AccountId,
+			org_id: T::AccountId,
+			metadata: Vec<u8>,
+			expiry: Option<T::BlockNumber>,
+		) -> Result<(), Error<T>> {
+			// check permisisno of authority
+			let _ = Self::get_authority(&sender)?;
+
+			// check whether org has been approved
+			ensure!(!Org::<T>::contains_key(&org_id), Error::<T>::AlreadyRegistered);
+
+			// check whether org has been revoked
+			ensure!(!OrgRevoked::<T>::contains_key(&org_id), Error::<T>::AlreadyRevoked);
+
+			Self::ensure_valid_expiry(expiry)?;
+
+			Self::ensure_can_approve_org(&sender)?;
+
+			// create system manager account
+			let org_account = Self::create_account(
+				Role::Organization,
+				Status::Active,
+				None,
+				None,
+				Some(sender.clone()),
+				metadata.clone(),
+			)?;
+
+			Org::<T>::insert(&org_id, org_account);
+			Self::add_org(&sender, &org_id);
+
+			OrgCnt::<T>::mutate(|cnt| *cnt = cnt.saturating_add(1));
+
+			Self::record_audit(
+				Some(sender.clone()),
+				AuditAction::Approve(OperationType::ORG),
+				Some(org_id.clone()),
+				Some(&metadata),
+			);
+
+			Self::set_mandate_expiry(&org_id, OperationType::ORG, expiry);
+
+			Self::deposit_event(Event::<T>::Approved {
+				target_id: org_id,
+				metadata,
+				approver: sender,
+			});
+
+			Ok(())
+		}
+
+		fn do_revoke_org(
+			sender: T::AccountId,
+			revoke_org_id: T::AccountId,
+			description: Vec<u8>,
+		) -> Result<(), Error<T>> {
+			Self::ensure_valid_description(&description)?;
+
+			// check permission of revoker
+			let authority = Self::get_authority(&sender)?;
+
+			let mut revoke_org = Self::get_account(&revoke_org_id, OperationType::ORG)?;
+
+			// ensure revoked org has not been revoked yet
+			ensure!(!OrgRevoked::<T>::contains_key(&revoke_org_id), Error::<T>::AlreadyRevoked);
+
+			Self::ensure_org_approver(&sender, &authority, &revoke_org)?;
+
+			// add revoked description to metadata object
+			revoke_org.metadata =
+				Self::add_metadata_field(&revoke_org.metadata, "revoke_description", &description)?;
+
+			SuspendedUntil::<T>::remove(&revoke_org_id, OperationType::ORG);
+			MandateExpiry::<T>::remove(&revoke_org_id, OperationType::ORG);
+
+			if let Some(approver) = &revoke_org.parent {
+				Self::remove_org(approver, &revoke_org_id);
+			}
+
+			// remove revoked sys man from Org Storage
+			Org::<T>::remove(&revoke_org_id);
+
+			// add revoked sys man to OrgRevoked Storage
+			OrgRevoked::<T>::insert(&revoke_org_id, revoke_org);
+
+			OrgCnt::<T>::mutate(|cnt| *cnt = cnt.saturating_sub(1));
+
+			Self::record_audit(
+				Some(sender.clone()),
+				AuditAction::Revoke(OperationType::ORG),
+				Some(revoke_org_id.clone()),
+				Some(&description),
+			);
+
+			// emit revoked event with information of revoked sys man
+			Self::deposit_event(Event::<T>::Revoked { target_id: revoke_org_id, revoker: sender });
+
+			Ok(())
+		}
+
+		fn do_reinstate_sys_man(
+			sender: T::AccountId,
+			reinstate_id: T::AccountId,
+			reason: Vec<u8>,
+		) -> Result<(), Error<T>> {
+			Self::ensure_valid_description(&reason)?;
+
+			let authority = Self::get_authority(&sender)?;
+
+			let mut reinstate_sys_man =
+				SysManRevoked::<T>::get(&reinstate_id).ok_or(Error::<T>::RevokedSysManNotExist)?;
+
+			// Ensure authority has higher hierarchical level than system manager to be reinstated
+			Self::ensure_outranks(&authority, &reinstate_sys_man)?;
+
+			// an account which has been approved again in the meantime can not be reinstated
+			ensure!(!SysMan::<T>::contains_key(&reinstate_id), Error::<T>::AlreadyRegistered);
+
+			// re-attach to the former parent if it is still active, otherwise to the reinstater
+			match Self::former_parent(&reinstate_sys_man) {
+				Some((parent_id, parent)) => {
+					Self::ensure_can_appoint(&parent)?;
+
+					reinstate_sys_man.parent = Some(parent_id.clone());
+
+					Self::add_child(&parent_id, &reinstate_id);
+				},
+				None => {
+					reinstate_sys_man.parent = Some(sender.clone());
+					reinstate_sys_man.level = Some(Self::ensure_can_appoint(&authority)?);
+
+					Self::add_child(&sender, &reinstate_id);
+				},
+			}
+
+			// keep only the children which are still active and still point to this account
+			reinstate_sys_man.children = reinstate_sys_man.children.map(|children| {
+				children
+					.into_iter()
+					.filter(|child| {
+						SysMan::<T>::get(child)
+							.map_or(false, |val| val.parent.as_ref() == Some(&reinstate_id))
+					})
+					.collect()
+			});
+
+			reinstate_sys_man.status = Status::Active;
+
+			SysManRevoked::<T>::remove(&reinstate_id);
+
+			SysMan::<T>::insert(&reinstate_id, reinstate_sys_man);
+
+			SysManCnt::<T>::mutate(|cnt| *cnt = cnt.saturating_add(1));
+
+			Self::record_audit(
+				Some(sender.clone()),
+				AuditAction::Reinstate(OperationType::SYS),
+				Some(reinstate_id.clone()),
+				Some(&reason),
+			);
+
+			SysManReinstated::<T>::insert(
+				&reinstate_id,
+				Reinstatement::<T> {
+					reinstated_by: sender.clone(),
+					reason: reason.clone(),
+					block: frame_system::Pallet::<T>::block_number(),
+				},
+			);
+
+			Self::deposit_event(Event::<T>::Reinstated {
+				target_id: reinstate_id,
+				reinstater: sender,
+				reason,
+			});
+
+			Ok(())
+		}
+
+		fn do_reinstate_org(
+			sender: T::AccountId,
+			reinstate_org_id: T::AccountId,
+			reason: Vec<u8>,
+		) -> Result<(), Error<T>> {
+			Self::ensure_valid_description(&reason)?;
+
+			let authority = Self::get_authority(&sender)?;
+
+			let mut reinstate_org =
+				OrgRevoked::<T>::get(&reinstate_org_id).ok_or(Error::<T>::RevokedOrgNotExist)?;
+
+			Self::ensure_org_approver(&sender, &authority, &reinstate_org)?;
+
+			// an org which has been approved again in the meantime can not be reinstated
+			ensure!(!Org::<T>::contains_key(&reinstate_org_id), Error::<T>::AlreadyRegistered);
+
+			// the reinstating manager, which sits above the approver, takes over an org whose
+			// approver is gone
+			let approver = reinstate_org
+				.parent
+				.as_ref()
+				.map(|parent| Self::current_key(parent, OperationType::SYS))
+				.filter(|parent| Self::is_active(parent, OperationType::SYS))
+				.unwrap_or_else(|| sender.clone());
+
+			Self::ensure_can_approve_org(&approver)?;
+
+			reinstate_org.status = Status::Active;
+			reinstate_org.parent = Some(approver.clone());
+
+			OrgRevoked::<T>::remove(&reinstate_org_id);
+
+			Org::<T>::insert(&reinstate_org_id, reinstate_org);
+			Self::add_org(&approver, &reinstate_org_id);
+
+			OrgCnt::<T>::mutate(|cnt| *cnt = cnt.saturating_add(1));
+
+			Self::record_audit(
+				Some(sender.clone()),
+				AuditAction::Reinstate(OperationType::ORG),
+				Some(reinstate_org_id.clone()),
+				Some(&reason),
+			);
+
+			OrgReinstated::<T>::insert(
+				&reinstate_org_id,
+				Reinstatement::<T> {
+					reinstated_by: sender.clone(),
+					reason: reason.clone(),
+					block: frame_system::Pallet::<T>::block_number(),
+				},
+			);
+
+			Self::deposit_event(Event::<T>::Reinstated {
+				target_id: reinstate_org_id,
+				reinstater: sender,
+				reason,
+			});
+
+			Ok(())
+		}
+
+		fn lift_suspensions(now: T::BlockNumber) -> Weight {
+			let expired = SuspensionExpiries::<T>::take(now);
+
+			for (target_id, op_type) in expired.iter() {
+				// the suspension may have been lifted or replaced in the meantime
+				if SuspendedUntil::<T>::get(target_id, op_type) != Some(now) {
+					continue
+				}
+
+				SuspendedUntil::<T>::remove(target_id, op_type);
+
+				if Self::set_status(target_id, *op_type, Status::Active).is_ok() {
+					Self::deposit_event(Event::<T>::Resumed { target_id: target_id.clone() });
+				}
+			}
+
+			let len = expired.len() as Weight;
+
+			T::DbWeight::get().reads_writes(1 + 2 * len, 1 + 2 * len)
+		}
+
+		fn expire_proposals(now: T::BlockNumber) -> Weight {
+			let expired = ProposalExpiries::<T>::take(now);
+
+			for proposal_id in expired.iter() {
+				// executed proposals have already been removed
+				if Proposals::<T>::take(proposal_id).is_some() {
+					Self::deposit_event(Event::<T>::ProposalExpired { proposal_id: *proposal_id });
+				}
+			}
+
+			let len = expired.len() as Weight;
+
+			T::DbWeight::get().reads_writes(1 + len, 1 + len)
+		}
+
+		fn execute_action(
+			proposer: T::AccountId,
+			action: ProposalAction<T::AccountId, T::BlockNumber>,
+		) -> Result<(), Error<T>> {
+			match action {
+				ProposalAction::ApproveSysMan { sys_man_id, metadata, expiry } =>
+					Self::do_approve_sys_man(proposer, sys_man_id, metadata, expiry),
+				ProposalAction::ApproveOrg { org_id, metadata, expiry } =>
+					Self::do_approve_org(proposer, org_id, metadata, expiry),
+				ProposalAction::RevokeOrg { org_id, description } =>
+					Self::do_revoke_org(proposer, org_id, description),
+				ProposalAction::ReinstateSysMan { sys_man_id, reason } =>
+					Self::do_reinstate_sys_man(proposer, sys_man_id, reason),
+				ProposalAction::ReinstateOrg { org_id, reason } =>
+					Self::do_reinstate_org(proposer, org_id, reason),
+			}
+		}
+
+		/// Checks that `sender` may revoke `revoke_id` and returns the account to be revoked.
+		fn ensure_can_revoke_sys_man(
+			sender: &T::AccountId,
+			revoke_id: &T::AccountId,
+		) -> Result<SysManAccount<T>, Error<T>> {
+			let authority = Self::get_authority(sender)?;
+
+			let revoke_sys_man = Self::get_account(revoke_id, OperationType::SYS)?;
+
+			// Ensure authority has higher hierarchical level than system manager to be revoked
+			Self::ensure_outranks(&authority, &revoke_sys_man)?;
+
+			// ensure revoked sys man has not been revoked yet
+			ensure!(!SysManRevoked::<T>::contains_key(revoke_id), Error::<T>::AlreadyRevoked);
+
+			Ok(revoke_sys_man)
+		}
+
+		/// Only the approver of an org and the managers above it can manage the org, orgs
+		/// approved before approvers were recorded are left to the root authorities.
+		fn ensure_org_approver(
+			sender: &T::AccountId,
+			authority: &SysManAccount<T>,
+			org: &SysManAccount<T>,
+		) -> Result<(), Error<T>> {
+			let is_approver = match &org.parent {
+				Some(approver) => Self::is_self_or_ancestor(sender, approver),
+				None => Self::level_of(authority)? == 0,
+			};
+
+			ensure!(is_approver, Error::<T>::NotOrgApprover);
+
+			Ok(())
+		}
+
+		/// Whether `ancestor` is `id` or one of the system managers above it.
+		fn is_self_or_ancestor(ancestor: &T::AccountId, id: &T::AccountId) -> bool {
+			let id = Self::current_key(id, OperationType::SYS);
+
+			*ancestor == id || Self::ancestors(&id).contains(ancestor)
+		}
+
+		/// Returns the system managers above `id`, from its parent up to the root authority.
+		/// Revoked managers are followed too, so a revoked manager does not cut off its own
+		/// superiors, and so are the keys its superiors have rotated to since.
+		pub fn ancestors(id: &T::AccountId) -> Vec<T::AccountId> {
+			let mut ancestors = Vec::new();
+			let mut current = id.clone();
+
+			// levels are stored as `u8`, so no valid chain is longer than this
+			for _ in 0..=u8::MAX {
+				let parent = SysMan::<T>::get(&current)
+					.or_else(|| SysManRevoked::<T>::get(&current))
+					.and_then(|val| val.parent)
+					.map(|parent| Self::current_key(&parent, OperationType::SYS));
+
+				match parent {
+					Some(parent) => {
+						ancestors.push(parent.clone());
+						current = parent;
+					},
+					None => break,
+				}
+			}
+
+			ancestors
+		}
+
+		/// Returns the former parent of a revoked system manager, under its current key, if it
+		/// is still active.
+		fn former_parent(account: &SysManAccount<T>) -> Option<(T::AccountId, SysManAccount<T>)> {
+			account.parent.as_ref().and_then(|parent_id| {
+				let parent_id = Self::current_key(parent_id, OperationType::SYS);
+
+				SysMan::<T>::get(&parent_id).map(|parent| (parent_id, parent))
+			})
+		}
+
+		/// Returns the level a revoked system manager gets back when `authority` reinstates it.
+		fn reinstated_level(
+			authority: &SysManAccount<T>,
+			account: &SysManAccount<T>,
+		) -> Result<u8, Error<T>> {
+			match Self::former_parent(account) {
+				Some(_) => Self::level_of(account),
+				None => Self::child_level(authority),
+			}
+		}
+
+		/// Returns the hierarchy level of a system manager account.
+		fn level_of(account: &SysManAccount<T>) -> Result<u8, Error<T>> {
+			account.level.ok_or(Error::<T>::MissingLevel)
+		}
+
+		/// Returns the level of a system manager appointed by `authority`.
+		fn child_level(authority: &SysManAccount<T>) -> Result<u8, Error<T>> {
+			Self::level_of(authority)?.checked_add(1).ok_or(Error::<T>::LevelOverflow)
+		}
+
+		fn get_open_proposal(proposal_id: ProposalId) -> Result<Proposal<T>, Error<T>> {
+			let proposal = Proposals::<T>::get(proposal_id).ok_or(Error::<T>::ProposalNotExist)?;
+
+			ensure!(
+				frame_system::Pallet::<T>::block_number() < proposal.expiry,
+				Error::<T>::ProposalExpired
+			);
+
+			Ok(proposal)
+		}
+
+		/// Only managers of the two top levels may endorse proposals, since managers further
+		/// down are appointed without any proposal.
+		fn ensure_can_endorse(who: &T::AccountId) -> Result<(), Error<T>> {
+			let authority = Self::get_authority(who)?;
+
+			ensure!(Self::level_of(&authority)? <= 1, Error::<T>::NotEligibleEndorser);
+
+			Ok(())
+		}
+
+		/// Counts the endorsements of a proposal that are still valid. Endorsements of managers
+		/// which have been revoked or suspended no longer count, and neither do endorsements of
+		/// managers appointed below another endorser.
+		fn count_endorsements(proposal: &Proposal<T>) -> u32 {
+			let endorsers = Self::current_endorsers(proposal);
+
+			endorsers
+				.iter()
+				.filter(|voter| Self::ensure_can_endorse(voter).is_ok())
+				.filter(|voter| {
+					!Self::ancestors(voter).iter().any(|ancestor| endorsers.contains(ancestor))
+				})
+				.count() as u32
+		}
+
+		/// Returns the endorsers of a proposal under their current keys, so endorsements
+		/// carry over a key rotation.
+		fn current_endorsers(proposal: &Proposal<T>) -> Vec<T::AccountId> {
+			proposal
+				.approvals
+				.iter()
+				.map(|voter| Self::current_key(voter, OperationType::SYS))
+				.collect()
+		}
+
+		/// Checks that `authority` may appoint one more system manager and returns its level.
+		fn ensure_can_appoint(authority: &SysManAccount<T>) -> Result<u8, Error<T>> {
+			let level = Self::child_level(authority)?;
+
+			ensure!(level <= Self::max_depth(), Error::<T>::MaxDepthExceeded);
+
+			let children = authority.children.as_ref().map_or(0, |children| children.len());
+
+			ensure!(children < Self::max_children() as usize, Error::<T>::TooManyChildren);
+
+			Ok(level)
+		}
+
+		/// Ensures `authority` sits strictly above `target` in the hierarchy.
+		fn ensure_outranks(
+			authority: &SysManAccount<T>,
+			target: &SysManAccount<T>,
+		) -> Result<(), Error<T>> {
+			ensure!(
+				Self::level_of(authority)? < Self::level_of(target)?,
+				Error::<T>::NoValidAuthorization
+			);
+
+			Ok(())
+		}
+
+		/// Moves a system manager from `SysMan` to `SysManRevoked`.
+		fn do_revoke_sys_man(
+			revoker: &T::AccountId,
+			revoke_id: &T::AccountId,
+			mut revoke_sys_man: SysManAccount<T>,
+			description: &[u8],
+		) -> Result<(), Error<T>> {
+			Self::ensure_valid_description(description)?;
+
+			// add revoked description to metadata object
+			revoke_sys_man.metadata = Self::add_metadata_field(
+				&revoke_sys_man.metadata,
+				"revoke_description",
+				description,
+			)?;
+
+			// detach revoked sys man from its parent
+			if let Some(parent) = &revoke_sys_man.parent {
+				Self::remove_child(parent, revoke_id);
+			}
+
+			SuspendedUntil::<T>::remove(revoke_id, OperationType::SYS);
+			MandateExpiry::<T>::remove(revoke_id, OperationType::SYS);
+
+			// remove revoked sys man from SysMan Storage
+			SysMan::<T>::remove(revoke_id);
+
+			// add revoked sys man to SysManRevoked Storage
+			SysManRevoked::<T>::insert(revoke_id, revoke_sys_man);
+
+			SysManCnt::<T>::mutate(|cnt| *cnt = cnt.saturating_sub(1));
+
+			Self::record_audit(
+				Some(revoker.clone()),
+				AuditAction::Revoke(OperationType::SYS),
+				Some(revoke_id.clone()),
+				Some(description),
+			);
+
+			Ok(())
+		}
+
+		/// Revokes up to `MaxCascadeRevocations` accounts from the queue of a cascading
+		/// revocation, storing whatever is left for a later call.
+		fn process_cascade(
+			root_id: T::AccountId,
+			mut cascade: CascadeRevocation<T>,
+		) -> Result<(), Error<T>> {
+			let mut processed = 0u32;
+
+			while processed < T::MaxCascadeRevocations::get() && !cascade.queue.is_empty() {
+				let target_id = cascade.queue.remove(0);
+
+				processed += 1;
+
+				// descendants which have been revoked in the meantime are skipped
+				if let Some(target) = SysMan::<T>::get(&target_id) {
+					cascade.queue.extend(target.children.clone().unwrap_or_default());
+
+					Self::do_revoke_sys_man(
+						&cascade.revoker,
+						&target_id,
+						target,
+						&cascade.description,
+					)?;
+
+					Self::deposit_event(Event::<T>::Revoked {
+						target_id,
+						revoker: cascade.revoker.clone(),
+					});
+				}
+			}
+
+			if cascade.queue.is_empty() {
+				PendingCascade::<T>::remove(&root_id);
+
+				Self::deposit_event(Event::<T>::CascadeRevocationCompleted { root_id });
+			} else {
+				let remaining = cascade.queue.len() as u32;
+
+				PendingCascade::<T>::insert(&root_id, cascade);
+
+				Self::deposit_event(Event::<T>::CascadeRevocationPending { root_id, remaining });
+			}
+
+			Ok(())
+		}
+
+		/// Returns up to `limit`, at most `MaxPageSize`, active system managers below `id` in
+		/// the hierarchy, in breadth-first order.
+		pub fn subtree(id: &T::AccountId, limit: u32) -> Vec<T::AccountId> {
+			let limit = limit.min(T::MaxPageSize::get()) as usize;
+
+			let mut subtree = Vec::new();
+			let mut visited = BTreeSet::new();
+			visited.insert(id.clone());
+
+			let mut queue: VecDeque<T::AccountId> = match SysMan::<T>::get(id) {
+				Some(val) => val.children.unwrap_or_default().into(),
+				None => return subtree,
+			};
+
+			while let Some(child) = queue.pop_front() {
+				if subtree.len() >= limit {
+					break
+				}
+
+				// guard against cycles in malformed genesis data
+				if !visited.insert(child.clone()) {
+					continue
+				}
+
+				if let Some(val) = SysMan::<T>::get(&child) {
+					queue.extend(val.children.unwrap_or_default());
+					subtree.push(child);
+				}
+			}
+
+			subtree
+		}
+
+		/// Returns the system managers appointed by `id` that are still active.
+		pub fn children_of(id: &T::AccountId) -> Vec<T::AccountId> {
+			SysMan::<T>::get(id)
+				.and_then(|val| val.children)
+				.unwrap_or_default()
+				.into_iter()
+				.filter(|child| SysMan::<T>::contains_key(child))
+				.collect()
+		}
+
+		/// Returns up to `limit`, at most `MaxPageSize`, active or revoked organizations in storage
+		/// order, starting after `start_after` or from the first one. Passing the last
+		/// organization of a page fetches the next one, even if organizations were added or
+		/// removed in the meantime.
+		pub fn orgs_page(
+			revoked: bool,
+			start_after: Option<T::AccountId>,
+			limit: u32,
+		) -> Vec<(T::AccountId, SysManAccount<T>)> {
+			let limit = limit.min(T::MaxPageSize::get()) as usize;
+
+			let orgs = match (revoked, start_after) {
+				(false, None) => Org::<T>::iter(),
+				(false, Some(last)) => Org::<T>::iter_from(Org::<T>::hashed_key_for(last)),
+				(true, None) => OrgRevoked::<T>::iter(),
+				(true, Some(last)) =>
+					OrgRevoked::<T>::iter_from(OrgRevoked::<T>::hashed_key_for(last)),
+			};
+
+			orgs.take(limit).collect()
+		}
+
+		fn add_child(parent: &T::AccountId, child: &T::AccountId) {
+			SysMan::<T>::mutate(parent, |maybe_parent| {
+				if let Some(parent) = maybe_parent {
+					let children = parent.children.get_or_insert_with(Vec::new);
+
+					if !children.contains(child) {
+						children.push(child.clone());
+					}
+				}
+			});
+		}
+
+		fn remove_child(parent: &T::AccountId, child: &T::AccountId) {
+			SysMan::<T>::mutate(parent, |maybe_parent| {
+				if let Some(children) = maybe_parent.as_mut().and_then(|p| p.children.as_mut()) {
+					children.retain(|c| c != child);
+				}
+			});
+		}
+
+		/// Checks that `approver` may take one more organization.
+		fn ensure_can_approve_org(approver: &T::AccountId) -> Result<(), Error<T>> {
+			ensure!(
+				OrgCntBySysMan::<T>::get(approver) < T::MaxOrgsPerSysMan::get(),
+				Error::<T>::TooManyOrgs
+			);
+
+			Ok(())
+		}
+
+		fn add_org(approver: &T::AccountId, org_id: &T::AccountId) {
+			OrgsBySysMan::<T>::insert(approver, org_id, ());
+			OrgCntBySysMan::<T>::mutate(approver, |cnt| *cnt = cnt.saturating_add(1));
+		}
+
+		fn remove_org(approver: &T::AccountId, org_id: &T::AccountId) {
+			if OrgsBySysMan::<T>::take(approver, org_id).is_some() {
+				OrgCntBySysMan::<T>::mutate(approver, |cnt| *cnt = cnt.saturating_sub(1));
+			}
+		}
+
+		/// Weight of `rotate_sys_man_key` for a manager with `children` appointed managers and
+		/// `orgs` approved organizations, audit log entry included.
+		fn rotate_sys_man_weight(children: u32, orgs: u32) -> Weight {
+			let (children, orgs) = (children as Weight, orgs as Weight);
+
+			10_000 +
+				T::DbWeight::get().reads_writes(12 + children + 2 * orgs, 16 + children + 3 * orgs)
+		}
+
+		/// Weight of `rotate_org_key` for an organization with `members` staff accounts, audit
+		/// log entry included.
+		fn rotate_org_weight(members: u32) -> Weight {
+			let members = members as Weight;
+
+			10_000 + T::DbWeight::get().reads_writes(10 + members, 14 + 2 * members)
+		}
+
+		/// Returns the system manager account of `id` if it may currently act as an authority.
+		pub fn get_authority(id: &T::AccountId) -> Result<SysManAccount<T>, Error<T>> {
+			let authority = Self::get_account(id, OperationType::SYS)?;
+
+			ensure!(authority.status == Status::Active, Error::<T>::AccountSuspended);
+
+			ensure!(!Self::is_expired(id, OperationType::SYS), Error::<T>::MandateExpired);
+
+			Ok(authority)
+		}
+
+		fn ensure_org_admin(org_id: &T::AccountId, who: &T::AccountId) -> Result<(), Error<T>> {
+			let org = Self::get_account(org_id, OperationType::ORG)?;
+
+			ensure!(org.status == Status::Active, Error::<T>::AccountSuspended);
+			ensure!(!Self::is_expired(org_id, OperationType::ORG), Error::<T>::MandateExpired);
+
+			ensure!(
+				Self::member_role(org_id, who) == Some(OrgRole::Admin),
+				Error::<T>::NotOrgAdmin
+			);
+
+			Ok(())
+		}
+
+		/// Whether `id` is an approved and currently active system manager or organization.
+		pub fn is_active(id: &T::AccountId, op_type: OperationType) -> bool {
+			Self::get_account(id, op_type).map_or(false, |val| val.status == Status::Active) &&
+				!Self::is_expired(id, op_type)
+		}
+
+		/// Whether the mandate of `id` has reached its expiry block.
+		pub fn is_expired(id: &T::AccountId, op_type: OperationType) -> bool {
+			MandateExpiry::<T>::get(id, op_type)
+				.map_or(false, |expiry| expiry <= frame_system::Pallet::<T>::block_number())
+		}
+
+		fn ensure_valid_expiry(expiry: Option<T::BlockNumber>) -> Result<(), Error<T>> {
+			if let Some(expiry) = expiry {
+				ensure!(
+					expiry > frame_system::Pallet::<T>::block_number(),
+					Error::<T>::InvalidExpiry
+				);
+			}
+
+			Ok(())
+		}
+
+		fn set_mandate_expiry(
+			id: &T::AccountId,
+			op_type: OperationType,
+			expiry: Option<T::BlockNumber>,
+		) {
+			match expiry {
+				Some(expiry) => {
+					// renewing to the block already scheduled must not schedule it twice
+					if MandateExpiry::<T>::get(id, op_type) != Some(expiry) {
+						MandateExpiries::<T>::append(expiry, (id.clone(), op_type));
+					}
+
+					MandateExpiry::<T>::insert(id, op_type, expiry);
+				},
+				None => MandateExpiry::<T>::remove(id, op_type),
+			}
+		}
+
+		/// Keys that have been rotated away from or to can not be rotated to again, so the
+		/// rotation history stays a simple chain.
+		fn ensure_unused_key(id: &T::AccountId, op_type: OperationType) -> Result<(), Error<T>> {
+			ensure!(
+				!RotatedTo::<T>::contains_key(id, op_type) &&
+					!RotatedFrom::<T>::contains_key(id, op_type),
+				Error::<T>::KeyAlreadyUsed
+			);
+
+			Ok(())
+		}
+
+		/// Follows the key rotations of `id` to the key it is currently known under. Keys can
+		/// only be rotated to once, so the rotations form a chain without cycles.
+		pub fn current_key(id: &T::AccountId, op_type: OperationType) -> T::AccountId {
+			let mut current = id.clone();
+
+			while let Some(next) = RotatedTo::<T>::get(&current, op_type) {
+				current = next;
+			}
+
+			current
+		}
+
+		/// Moves the suspension and mandate of `old_id` to `new_id` and links both keys.
+		fn move_key(old_id: &T::AccountId, new_id: &T::AccountId, op_type: OperationType) {
+			if let Some(until) = SuspendedUntil::<T>::take(old_id, op_type) {
+				SuspendedUntil::<T>::insert(new_id, op_type, until);
+				SuspensionExpiries::<T>::append(until, (new_id.clone(), op_type));
+			}
+
+			if let Some(expiry) = MandateExpiry::<T>::take(old_id, op_type) {
+				Self::set_mandate_expiry(new_id, op_type, Some(expiry));
+			}
+
+			RotatedTo::<T>::insert(old_id, op_type, new_id);
+			RotatedFrom::<T>::insert(new_id, op_type, old_id);
+
+			Self::record_audit(
+				Some(old_id.clone()),
+				AuditAction::RotateKey(op_type),
+				Some(new_id.clone()),
+				None,
+			);
+
+			Self::deposit_event(Event::<T>::KeyRotated {
+				old_id: old_id.clone(),
+				new_id: new_id.clone(),
+				op_type,
+			});
+		}
+
+		fn do_renew(
+			renewer: T::AccountId,
+			target_id: T::AccountId,
+			op_type: OperationType,
+			expiry: Option<T::BlockNumber>,
+		) -> Result<(), Error<T>> {
+			Self::ensure_valid_expiry(expiry)?;
+
+			Self::set_mandate_expiry(&target_id, op_type, expiry);
+
+			Self::record_audit(
+				Some(renewer),
+				AuditAction::Renew(op_type),
+				Some(target_id.clone()),
+				None,
+			);
+
+			Self::deposit_event(Event::<T>::MandateRenewed { target_id, op_type, expiry });
+
+			Ok(())
+		}
+
+		fn expire_mandates(now: T::BlockNumber) -> Weight {
+			let expired = MandateExpiries::<T>::take(now);
+
+			for (index, (target_id, op_type)) in expired.iter().enumerate() {
+				// a mandate renewed away and back is listed more than once
+				if expired[..index].contains(&(target_id.clone(), *op_type)) {
+					continue
+				}
+
+				// the mandate may have been renewed or the account revoked in the meantime
+				if MandateExpiry::<T>::get(target_id, op_type) == Some(now) {
+					Self::deposit_event(Event::<T>::MandateExpired {
+						target_id: target_id.clone(),
+						op_type: *op_type,
+					});
+				}
+			}
+
+			let len = expired.len() as Weight;
+
+			T::DbWeight::get().reads_writes(1 + len, 1)
+		}
+
+		fn set_status(
+			id: &T::AccountId,
+			op_type: OperationType,
+			status: Status,
+		) -> Result<(), Error<T>> {
+			let update = |maybe_account: &mut Option<SysManAccount<T>>| match maybe_account {
+				Some(account) => {
+					account.status = status;
+					Ok(())
+				},
+				None => Err(Error::<T>::NoneValue),
+			};
+
+			match op_type {
+				OperationType::SYS => SysMan::<T>::try_mutate(id, update),
+				OperationType::ORG => Org::<T>::try_mutate(id, update),
+			}
+		}
+
+		fn do_suspend(
+			suspender: T::AccountId,
+			target_id: T::AccountId,
+			target: SysManAccount<T>,
+			op_type: OperationType,
+			until: Option<T::BlockNumber>,
+		) -> Result<(), Error<T>> {
+			ensure!(target.status == Status::Active, Error::<T>::AlreadySuspended);
+
+			if let Some(until) = until {
+				ensure!(
+					until > frame_system::Pallet::<T>::block_number(),
+					Error::<T>::InvalidSuspensionPeriod
+				);
+
+				SuspendedUntil::<T>::insert(&target_id, op_type, until);
+				SuspensionExpiries::<T>::append(until, (target_id.clone(), op_type));
+			}
+
+			Self::set_status(&target_id, op_type, Status::Deactivated)?;
+
+			Self::record_audit(
+				Some(suspender.clone()),
+				AuditAction::Suspend(op_type),
+				Some(target_id.clone()),
+				None,
+			);
+
+			Self::deposit_event(Event::<T>::Suspended { target_id, suspender, until });
+
+			Ok(())
+		}
+
+		fn do_resume(
+			resumer: T::AccountId,
+			target_id: T::AccountId,
+			target: SysManAccount<T>,
+			op_type: OperationType,
+		) -> Result<(), Error<T>> {
+			ensure!(target.status == Status::Deactivated, Error::<T>::NotSuspended);
+
+			// the pending entry in `SuspensionExpiries` is ignored once this is gone
+			SuspendedUntil::<T>::remove(&target_id, op_type);
+
+			Self::set_status(&target_id, op_type, Status::Active)?;
+
+			Self::record_audit(
+				Some(resumer),
+				AuditAction::Resume(op_type),
+				Some(target_id.clone()),
+				None,
+			);
+
+			Self::deposit_event(Event::<T>::Resumed { target_id });
+
+			Ok(())
+		}
+
+		fn record_audit(
+			actor: Option<T::AccountId>,
+			action: AuditAction<T::AccountId>,
+			target: Option<T::AccountId>,
+			description: Option<&[u8]>,
+		) {
+			let audit_id = AuditLogCnt::<T>::get();
+
+			// index the entry under the actor and the target, once if they are the same
+			let mut accounts = actor.iter().chain(target.iter()).cloned().collect::<Vec<_>>();
+			accounts.dedup();
+
+			for account in accounts {
+				let index = AuditLogByAccountCnt::<T>::get(&account);
+
+				AuditLogByAccount::<T>::insert(&account, index, audit_id);
+				AuditLogByAccountCnt::<T>::insert(&account, index.saturating_add(1));
+			}
+
+			AuditLog::<T>::insert(
+				audit_id,
+				AuditEntry {
+					actor,
+					action,
+					target,
+					block: frame_system::Pallet::<T>::block_number(),
+					timestamp: T::UnixTime::now().as_millis() as u64,
+					description_hash: description.map(T::Hashing::hash),
+				},
+			);
+			AuditLogCnt::<T>::put(audit_id.saturating_add(1));
+		}
+
+		/// Returns up to `limit`, at most `MaxPageSize`, audit log entries, oldest first, starting
+		/// at `start`.
+		pub fn audit_log_page(start: AuditId, limit: u32) -> Vec<(AuditId, AuditEntryOf<T>)> {
+			let limit = limit.min(T::MaxPageSize::get());
+
+			let end = start.saturating_add(limit as AuditId).min(AuditLogCnt::<T>::get());
+
+			(start..end)
+				.filter_map(|audit_id| AuditLog::<T>::get(audit_id).map(|entry| (audit_id, entry)))
+				.collect()
+		}
+
+		/// Returns up to `limit`, at most `MaxPageSize`, audit log entries `account` took part
+		/// in, oldest first, skipping the first `start` of them.
+		pub fn audit_log_page_of(
+			account: &T::AccountId,
+			start: u32,
+			limit: u32,
+		) -> Vec<(AuditId, AuditEntryOf<T>)> {
+			let limit = limit.min(T::MaxPageSize::get());
+
+			let end = start.saturating_add(limit).min(AuditLogByAccountCnt::<T>::get(account));
+
+			(start..end)
+				.filter_map(|index| AuditLogByAccount::<T>::get(account, index))
+				.filter_map(|audit_id| AuditLog::<T>::get(audit_id).map(|entry| (audit_id, entry)))
+				.collect()
+		}
+
+		pub fn str2vec(s: &str) -> Vec<u8> {
+			s.as_bytes().to_vec()
+		}
+
+		/// Checks that `metadata` is a JSON object that satisfies the schema of `op_type`.
+		pub fn validate_metadata(metadata: &[u8], op_type: OperationType) -> Result<(), Error<T>> {
+			ensure!(
+				metadata.len() <= T::MaxMetadataLength::get() as usize,
+				Error::<T>::MetadataTooLong
+			);
+
+			let map = match serde_json::from_slice::<Value>(metadata) {
+				Ok(Value::Object(map)) => map,
+				_ => Err(Error::<T>::MetadataNotJsonObject)?,
+			};
+
+			for (key, field_type) in MetadataSchemas::<T>::get(op_type).required_fields.iter() {
+				let key = str::from_utf8(key).map_err(|_| Error::<T>::InvalidMetadataSchema)?;
+
+				let value = map.get(key).ok_or(Error::<T>::MetadataMissingField)?;
+
+				ensure!(field_type.matches(value), Error::<T>::MetadataInvalidFieldType);
+			}
+
+			Ok(())
+		}
+
+		/// Checks the length and encoding of the description or reason given with a revocation
+		/// or reinstatement.
+		fn ensure_valid_description(description: &[u8]) -> Result<(), Error<T>> {
+			ensure!(
+				description.len() <= T::MaxDescriptionLength::get() as usize,
+				Error::<T>::DescriptionTooLong
+			);
+
+			str::from_utf8(description).map_err(|_| Error::<T>::DescriptionNotUtf8)?;
+
+			Ok(())
+		}
+
+		/// Checks the metadata, description or reason carried by a proposal before it is stored.
+		fn ensure_valid_action(
+			action: &ProposalAction<T::AccountId, T::BlockNumber>,
+		) -> Result<(), Error<T>> {
+			match action {
+				ProposalAction::ApproveSysMan { metadata, .. } =>
+					Self::validate_metadata(metadata, OperationType::SYS),
+				ProposalAction::ApproveOrg { metadata, .. } =>
+					Self::validate_metadata(metadata, OperationType::ORG),
+				ProposalAction::RevokeOrg { description, .. } =>
+					Self::ensure_valid_description(description),
+				ProposalAction::ReinstateSysMan { reason, .. } |
+				ProposalAction::ReinstateOrg { reason, .. } => Self::ensure_valid_description(reason),
+			}
+		}
+
+		/// Merges a string field into the JSON object stored in `metadata`. The value is cut
+		/// short to keep the result within `MaxMetadataLength`, and left out if even an empty
+		/// one doesn't fit, so that a revocation is never blocked by the size of the metadata.
+		fn add_metadata_field(
+			metadata: &[u8],
+			field_key: &str,
+			field_val: &[u8],
+		) -> Result<Vec<u8>, Error<T>> {
+			let mut field_val =
+				str::from_utf8(field_val).map_err(|_| Error::<T>::DescriptionNotUtf8)?;
+
+			let original = metadata;
+
+			let metadata = match serde_json::from_slice::<Value>(metadata) {
+				Ok(value @ Value::Object(_)) => value,
+				// metadata stored before it was validated is kept as a plain string
+				_ => {
+					let mut map = Map::new();
+
+					map.insert(
+						"legacy_metadata".to_string(),
+						Value::String(String::from_utf8_lossy(metadata).into_owned()),
+					);
+
+					Value::Object(map)
+				},
+			};
+
+			let max_len = T::MaxMetadataLength::get() as usize;
+
+			loop {
+				let merged = Self::add_json_field(&metadata, field_key, field_val).to_string();
+
+				if merged.len() <= max_len {
+					return Ok(merged.into_bytes())
+				}
+
+				if field_val.is_empty() {
+					return Ok(original.to_vec())
+				}
+
+				// drop the overflow from the end of the value, escaping may need another round
+				let mut end = field_val.len().saturating_sub(merged.len() - max_len);
+
+				while !field_val.is_char_boundary(end) {
+					end -= 1;
+				}
+
+				field_val = &field_val[..end];
+			}
+		}
+
+		fn add_json_field(v: &Value, field_key: &str, field_val: &str) -> Value {
+			match v {
+				Value::Object(map) => {
+					let mut map = map.clone();
+
+					map.insert(field_key.to_string(), Value::String(field_val.to_string()));
+
+					Value::Object(map)
+				},
+				v => v.clone(),
+			}
+		}
+	}
+
+	impl<T: Config> SysManAuthority<T::AccountId> for Pallet<T> {
+		fn is_authority(who: &T::AccountId) -> bool {
+			Self::get_authority(who).is_ok()
+		}
+
+		fn has_role(who: &T::AccountId, role: &Role) -> bool {
+			match role {
+				Role::SysMan => Self::is_active(who, OperationType::SYS),
+				Role::Organization => Self::is_active(who, OperationType::ORG),
+				Role::User => true,
+			}
+		}
+	}
+
+	impl<T: Config> OrgMembership<T::AccountId> for Pallet<T> {
+		fn current_org_key(org: &T::AccountId) -> T::AccountId {
+			Self::current_key(org, OperationType::ORG)
+		}
+
+		fn is_active_org(org: &T::AccountId) -> bool {
+			Self::is_active(org, OperationType::ORG)
+		}
+
+		fn member_role(org: &T::AccountId, who: &T::AccountId) -> Option<OrgRole> {
+			if !Self::is_active(org, OperationType::ORG) {
+				return None
+			}
+
+			if org == who {
+				return Some(OrgRole::Admin)
+			}
+
+			OrgMembers::<T>::get(org, who)
 		}
 	}
 }
@@ -0,0 +1,35 @@
+//! Storage migrations of the sys-man pallet.
+
+use super::*;
+use frame_support::{pallet_prelude::*, traits::StorageVersion};
+
+/// Recounts the system managers and organizations, and indexes the organizations by the
+/// system manager that approved them.
+pub mod v1 {
+	use super::*;
+
+	pub fn migrate<T: Config>() -> Weight {
+		if StorageVersion::get::<Pallet<T>>() >= 1 {
+			return T::DbWeight::get().reads(1)
+		}
+
+		let sys_men = SysMan::<T>::iter_keys().count() as u64;
+
+		let mut orgs = 0u64;
+		for (org_id, org) in Org::<T>::iter() {
+			orgs += 1;
+			if let Some(approver) = org.parent {
+				if !OrgsBySysMan::<T>::contains_key(&approver, &org_id) {
+					OrgsBySysMan::<T>::insert(&approver, &org_id, ());
+					OrgCntBySysMan::<T>::mutate(&approver, |cnt| *cnt = cnt.saturating_add(1));
+				}
+			}
+		}
+
+		SysManCnt::<T>::put(sys_men);
+		OrgCnt::<T>::put(orgs);
+
+		STORAGE_VERSION.put::<Pallet<T>>();
+		T::DbWeight::get().reads_writes(sys_men + 3 * orgs + 1, 2 * orgs + 3)
+	}
+}
@@ -1,7 +1,7 @@
 //! mock setup for testing pallet-sys-man functionalities
 
 use crate as pallet_sys_man;
-use frame_support::parameter_types;
+use frame_support::{parameter_types, traits::UnixTime};
 use frame_system as system;
 use sp_core::H256;
 use sp_runtime::{
@@ -27,6 +27,17 @@ frame_support::construct_runtime!(
 parameter_types! {
 	pub const BlockHashCount: u64 = 250;
 	pub const SS58Prefix: u8 = 42;
+	pub const MaxCascadeRevocations: u32 = 2;
+	pub const MaxDepth: u8 = 4;
+	pub const MaxChildren: u32 = 3;
+	pub const MaxChildrenLimit: u32 = 5;
+	pub const MaxOrgsPerSysMan: u32 = 5;
+	pub const ApprovalThreshold: u32 = 1;
+	pub const ProposalLifetime: u64 = 10;
+	pub const MaxOrgMembers: u32 = 3;
+	pub const MaxMetadataLength: u32 = 512;
+	pub const MaxDescriptionLength: u32 = 256;
+	pub const MaxPageSize: u32 = 3;
 }
 
 impl system::Config for Test {
@@ -57,6 +68,27 @@ impl system::Config for Test {
 
 impl pallet_sys_man::Config for Test {
 	type Event = Event;
+	type MaxCascadeRevocations = MaxCascadeRevocations;
+	type MaxMetadataLength = MaxMetadataLength;
+	type MaxDescriptionLength = MaxDescriptionLength;
+	type MaxPageSize = MaxPageSize;
+	type MaxDepth = MaxDepth;
+	type MaxChildren = MaxChildren;
+	type MaxChildrenLimit = MaxChildrenLimit;
+	type MaxOrgsPerSysMan = MaxOrgsPerSysMan;
+	type ApprovalThreshold = ApprovalThreshold;
+	type ProposalLifetime = ProposalLifetime;
+	type MaxOrgMembers = MaxOrgMembers;
+	type UnixTime = MockUnixTime;
+}
+
+/// Six second blocks starting at the unix epoch.
+pub struct MockUnixTime;
+
+impl UnixTime for MockUnixTime {
+	fn now() -> core::time::Duration {
+		core::time::Duration::from_secs(System::block_number() * 6)
+	}
 }
 
 // Build genesis storage according to the mock runtime.
@@ -1,10 +1,17 @@
 //! pallet-sys-man functionalities test
 use super::*;
 use crate::{mock::*, Error};
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{Hooks, StorageVersion},
+};
 use frame_system as system;
-use pallet_utils::{Role, Status};
-use serde_json::{json, Value};
+use pallet_utils::{
+	traits::{OrgMembership, SysManAuthority},
+	OrgRole, Role, Status,
+};
+use serde_json::Value;
+use sp_runtime::traits::{BlakeTwo256, Hash};
 
 fn str2vec(s: &str) -> Vec<u8> {
 	s.as_bytes().to_vec()
@@ -25,8 +32,8 @@ fn generate_test_account(
 		metadata: str2vec(
 			r#"
 			{
-				"description": "Root authority",
-        	}"#,
+				"description": "Root authority"
+			}"#,
 		),
 	};
 
@@ -65,9 +72,9 @@ fn approve_sys_man_should_work() {
 		);
 
 		// Dispatch a signed extrinsic.
-		assert_ok!(SysManModule::approve_sys_man(Origin::signed(1), 2u64, metadata.clone()));
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(1), 2u64, metadata.clone(), None));
 
-		let _ = SysManModule::approve_sys_man(Origin::signed(1), 2u64, metadata.clone());
+		let _ = SysManModule::approve_sys_man(Origin::signed(1), 2u64, metadata.clone(), None);
 
 		let new_sys_man = SysMan::<Test>::get(2).unwrap();
 
@@ -78,6 +85,28 @@ fn approve_sys_man_should_work() {
 	});
 }
 
+#[test]
+fn approve_sys_man_should_update_children() {
+	new_test_ext().execute_with(|| {
+		let root_authority = generate_test_account(Role::SysMan, Some(0), None, None);
+
+		SysMan::<Test>::insert(&1u64, root_authority);
+
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(1), 2u64, str2vec("{}"), None));
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(1), 3u64, str2vec("{}"), None));
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(2), 4u64, str2vec("{}"), None));
+
+		assert_eq!(SysMan::<Test>::get(1).unwrap().children, Some(vec![2u64, 3u64]));
+		assert_eq!(SysMan::<Test>::get(2).unwrap().children, Some(vec![4u64]));
+		assert_eq!(SysMan::<Test>::get(4).unwrap().parent, Some(2u64));
+
+		assert_eq!(SysManModule::subtree(&1u64, 10), vec![2u64, 3u64, 4u64]);
+		assert_eq!(SysManModule::subtree(&1u64, 2), vec![2u64, 3u64]);
+		assert_eq!(SysManModule::subtree(&2u64, 10), vec![4u64]);
+		assert_eq!(SysManModule::subtree(&3u64, 10), Vec::<u64>::new());
+	});
+}
+
 #[test]
 fn approve_sys_man_should_fails() {
 	// already_exists
@@ -98,7 +127,7 @@ fn approve_sys_man_should_fails() {
 		);
 
 		assert_noop!(
-			SysManModule::approve_sys_man(Origin::signed(1), 1u64, metadata.clone()),
+			SysManModule::approve_sys_man(Origin::signed(1), 1u64, metadata.clone(), None),
 			Error::<Test>::AlreadyRegistered
 		);
 	});
@@ -122,7 +151,7 @@ fn approve_sys_man_should_fails() {
 		);
 
 		assert_noop!(
-			SysManModule::approve_sys_man(Origin::signed(1), 2u64, metadata.clone()),
+			SysManModule::approve_sys_man(Origin::signed(1), 2u64, metadata.clone(), None),
 			Error::<Test>::NoValidAuthorization
 		);
 	})
@@ -159,6 +188,71 @@ fn revole_sys_man_should_work() {
 	})
 }
 
+#[test]
+fn revoke_sys_man_should_update_children() {
+	new_test_ext().execute_with(|| {
+		let root_authority = generate_test_account(Role::SysMan, Some(0), None, None);
+
+		SysMan::<Test>::insert(&1u64, root_authority);
+
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(1), 2u64, str2vec("{}"), None));
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(1), 3u64, str2vec("{}"), None));
+
+		assert_ok!(SysManModule::revoke_sys_man(Origin::signed(1), 2u64, str2vec("revoked")));
+
+		assert_eq!(SysMan::<Test>::get(1).unwrap().children, Some(vec![3u64]));
+		assert_eq!(SysManModule::subtree(&1u64, 10), vec![3u64]);
+	});
+}
+
+#[test]
+fn revoke_sys_man_cascade_should_work() {
+	new_test_ext().execute_with(|| {
+		let root_authority = generate_test_account(Role::SysMan, Some(0), None, None);
+
+		SysMan::<Test>::insert(&1u64, root_authority);
+
+		// 1 -> 2 -> {3, 4}, 3 -> 5, 1 -> 6
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(1), 2u64, str2vec("{}"), None));
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(2), 3u64, str2vec("{}"), None));
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(2), 4u64, str2vec("{}"), None));
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(3), 5u64, str2vec("{}"), None));
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(1), 6u64, str2vec("{}"), None));
+
+		assert_ok!(SysManModule::revoke_sys_man_cascade(
+			Origin::signed(1),
+			2u64,
+			str2vec("leaked key")
+		));
+
+		// root of the subtree and the first `MaxCascadeRevocations` descendants are revoked
+		assert!(SysManRevoked::<Test>::contains_key(&2u64));
+		assert!(SysManRevoked::<Test>::contains_key(&3u64));
+		assert!(SysManRevoked::<Test>::contains_key(&4u64));
+		assert!(SysMan::<Test>::contains_key(&5u64));
+		assert_eq!(SysManModule::pending_cascade(&2u64).unwrap().queue, vec![5u64]);
+
+		System::assert_last_event(mock::Event::SysManModule(
+			crate::Event::CascadeRevocationPending { root_id: 2u64, remaining: 1 },
+		));
+
+		assert_ok!(SysManModule::continue_cascade_revocation(Origin::signed(6), 2u64));
+
+		assert!(SysManRevoked::<Test>::contains_key(&5u64));
+		assert!(SysManModule::pending_cascade(&2u64).is_none());
+		assert_eq!(SysManModule::subtree(&1u64, 10), vec![6u64]);
+
+		System::assert_last_event(mock::Event::SysManModule(
+			crate::Event::CascadeRevocationCompleted { root_id: 2u64 },
+		));
+
+		assert_noop!(
+			SysManModule::continue_cascade_revocation(Origin::signed(6), 2u64),
+			Error::<Test>::CascadeNotPending
+		);
+	});
+}
+
 #[test]
 fn revoke_sys_man_should_fail() {
 	// not exist
@@ -251,9 +345,9 @@ fn approve_org_should_work() {
 		);
 
 		// Dispatch a signed extrinsic.
-		assert_ok!(SysManModule::approve_org(Origin::signed(1), 1u64, metadata.clone()));
+		assert_ok!(SysManModule::approve_org(Origin::signed(1), 1u64, metadata.clone(), None));
 
-		let _ = SysManModule::approve_org(Origin::signed(1), 1u64, metadata.clone());
+		let _ = SysManModule::approve_org(Origin::signed(1), 1u64, metadata.clone(), None);
 
 		let new_org = Org::<Test>::get(1).unwrap();
 
@@ -281,7 +375,7 @@ fn approve_org_should_fails() {
 
 		// Dispatch a signed extrinsic.
 		assert_noop!(
-			SysManModule::approve_org(Origin::signed(1), 1u64, metadata.clone()),
+			SysManModule::approve_org(Origin::signed(1), 1u64, metadata.clone(), None),
 			Error::<Test>::SysManNotExist
 		);
 	});
@@ -311,7 +405,7 @@ fn approve_org_should_fails() {
 
 		// Dispatch a signed extrinsic.
 		assert_noop!(
-			SysManModule::approve_org(Origin::signed(1), id, metadata.clone()),
+			SysManModule::approve_org(Origin::signed(1), id, metadata.clone(), None),
 			Error::<Test>::AlreadyRegistered
 		);
 	});
@@ -341,7 +435,7 @@ fn approve_org_should_fails() {
 
 		// Dispatch a signed extrinsic.
 		assert_noop!(
-			SysManModule::approve_org(Origin::signed(1), id, metadata.clone()),
+			SysManModule::approve_org(Origin::signed(1), id, metadata.clone(), None),
 			Error::<Test>::AlreadyRevoked
 		);
 	})
@@ -374,7 +468,7 @@ fn revoke_org_should_work() {
 			str2vec(description.clone()),
 		);
 
-		let mut metadata = json!(org.metadata);
+		let mut metadata: Value = serde_json::from_slice(&org.metadata).unwrap();
 
 		metadata = add_json_field(&metadata, "revoke_description".to_string(), description);
 
@@ -426,3 +520,1364 @@ fn revoke_org_should_fail() {
 		);
 	})
 }
+
+#[test]
+fn reinstate_sys_man_should_work() {
+	new_test_ext().execute_with(|| {
+		let root_authority = generate_test_account(Role::SysMan, Some(0), None, None);
+
+		SysMan::<Test>::insert(&1u64, root_authority);
+
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(1), 2u64, str2vec("{}"), None));
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(2), 3u64, str2vec("{}"), None));
+		assert_ok!(SysManModule::revoke_sys_man(Origin::signed(1), 2u64, str2vec("mistake")));
+
+		assert_ok!(SysManModule::reinstate_sys_man(
+			Origin::signed(1),
+			2u64,
+			str2vec("revoked by mistake")
+		));
+
+		let reinstated = SysMan::<Test>::get(2).unwrap();
+
+		assert_eq!(reinstated.status, Status::Active);
+		assert_eq!(reinstated.parent, Some(1u64));
+		assert_eq!(reinstated.children, Some(vec![3u64]));
+		assert_eq!(false, SysManRevoked::<Test>::contains_key(&2u64));
+		assert_eq!(SysManModule::subtree(&1u64, 10), vec![2u64, 3u64]);
+
+		let reinstatement = SysManModule::sys_man_reinstated(&2u64).unwrap();
+
+		assert_eq!(reinstatement.reinstated_by, 1u64);
+		assert_eq!(reinstatement.reason, str2vec("revoked by mistake"));
+		assert_eq!(reinstatement.block, 1);
+	});
+}
+
+#[test]
+fn reinstate_sys_man_should_fail() {
+	// not revoked
+	new_test_ext().execute_with(|| {
+		let root_authority = generate_test_account(Role::SysMan, Some(0), None, None);
+
+		SysMan::<Test>::insert(&1u64, root_authority);
+
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(1), 2u64, str2vec("{}"), None));
+
+		assert_noop!(
+			SysManModule::reinstate_sys_man(Origin::signed(1), 2u64, str2vec("reason")),
+			Error::<Test>::RevokedSysManNotExist
+		);
+	});
+
+	// no authorization
+	new_test_ext().execute_with(|| {
+		let root_authority = generate_test_account(Role::SysMan, Some(0), None, None);
+
+		SysMan::<Test>::insert(&1u64, root_authority);
+
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(1), 2u64, str2vec("{}"), None));
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(1), 3u64, str2vec("{}"), None));
+		assert_ok!(SysManModule::revoke_sys_man(Origin::signed(1), 2u64, str2vec("reason")));
+
+		assert_noop!(
+			SysManModule::reinstate_sys_man(Origin::signed(3), 2u64, str2vec("reason")),
+			Error::<Test>::NoValidAuthorization
+		);
+	});
+}
+
+#[test]
+fn reinstate_org_should_work() {
+	new_test_ext().execute_with(|| {
+		let authority = generate_test_account(Role::SysMan, Some(0), None, None);
+
+		SysMan::<Test>::insert(&1u64, authority);
+
+		assert_ok!(SysManModule::approve_org(Origin::signed(1), 2u64, str2vec("{}"), None));
+		assert_ok!(SysManModule::revoke_org(Origin::signed(1), 2u64, str2vec("reason")));
+
+		// a revoked organization can not be approved again
+		assert_noop!(
+			SysManModule::approve_org(Origin::signed(1), 2u64, str2vec("{}"), None),
+			Error::<Test>::AlreadyRevoked
+		);
+
+		assert_ok!(SysManModule::reinstate_org(Origin::signed(1), 2u64, str2vec("reason")));
+
+		assert_eq!(Org::<Test>::get(2).unwrap().status, Status::Active);
+		assert_eq!(false, OrgRevoked::<Test>::contains_key(&2u64));
+		assert_eq!(SysManModule::org_reinstated(&2u64).unwrap().reinstated_by, 1u64);
+
+		assert_noop!(
+			SysManModule::reinstate_org(Origin::signed(1), 2u64, str2vec("reason")),
+			Error::<Test>::RevokedOrgNotExist
+		);
+	});
+}
+
+#[test]
+fn suspend_sys_man_should_work() {
+	new_test_ext().execute_with(|| {
+		let root_authority = generate_test_account(Role::SysMan, Some(0), None, None);
+
+		SysMan::<Test>::insert(&1u64, root_authority);
+
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(1), 2u64, str2vec("{}"), None));
+
+		assert_ok!(SysManModule::suspend_sys_man(Origin::signed(1), 2u64, None));
+
+		assert_eq!(SysMan::<Test>::get(2).unwrap().status, Status::Deactivated);
+		assert_eq!(false, SysManModule::is_active(&2u64, OperationType::SYS));
+
+		// a suspended sys man can not act as an authority
+		assert_noop!(
+			SysManModule::approve_sys_man(Origin::signed(2), 3u64, str2vec("{}"), None),
+			Error::<Test>::AccountSuspended
+		);
+		assert_noop!(
+			SysManModule::approve_org(Origin::signed(2), 3u64, str2vec("{}"), None),
+			Error::<Test>::AccountSuspended
+		);
+
+		assert_noop!(
+			SysManModule::suspend_sys_man(Origin::signed(1), 2u64, None),
+			Error::<Test>::AlreadySuspended
+		);
+
+		assert_ok!(SysManModule::resume_sys_man(Origin::signed(1), 2u64));
+
+		assert_eq!(SysMan::<Test>::get(2).unwrap().status, Status::Active);
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(2), 3u64, str2vec("{}"), None));
+
+		assert_noop!(
+			SysManModule::resume_sys_man(Origin::signed(1), 2u64),
+			Error::<Test>::NotSuspended
+		);
+	});
+}
+
+#[test]
+fn suspension_should_end_at_given_block() {
+	new_test_ext().execute_with(|| {
+		let authority = generate_test_account(Role::SysMan, Some(0), None, None);
+
+		SysMan::<Test>::insert(&1u64, authority);
+
+		assert_ok!(SysManModule::approve_org(Origin::signed(1), 2u64, str2vec("{}"), None));
+
+		assert_noop!(
+			SysManModule::suspend_org(Origin::signed(1), 2u64, Some(1)),
+			Error::<Test>::InvalidSuspensionPeriod
+		);
+
+		assert_ok!(SysManModule::suspend_org(Origin::signed(1), 2u64, Some(5)));
+
+		assert_eq!(Org::<Test>::get(2).unwrap().status, Status::Deactivated);
+		assert_eq!(SysManModule::suspended_until(&2u64, OperationType::ORG), Some(5));
+
+		SysManModule::on_initialize(4);
+		assert_eq!(Org::<Test>::get(2).unwrap().status, Status::Deactivated);
+
+		SysManModule::on_initialize(5);
+		assert_eq!(Org::<Test>::get(2).unwrap().status, Status::Active);
+		assert_eq!(SysManModule::suspended_until(&2u64, OperationType::ORG), None);
+	});
+}
+
+#[test]
+fn resumed_suspension_should_not_end_later_suspension() {
+	new_test_ext().execute_with(|| {
+		let authority = generate_test_account(Role::SysMan, Some(0), None, None);
+
+		SysMan::<Test>::insert(&1u64, authority);
+
+		assert_ok!(SysManModule::approve_org(Origin::signed(1), 2u64, str2vec("{}"), None));
+
+		assert_ok!(SysManModule::suspend_org(Origin::signed(1), 2u64, Some(5)));
+		assert_ok!(SysManModule::resume_org(Origin::signed(1), 2u64));
+		assert_ok!(SysManModule::suspend_org(Origin::signed(1), 2u64, None));
+
+		SysManModule::on_initialize(5);
+
+		assert_eq!(Org::<Test>::get(2).unwrap().status, Status::Deactivated);
+	});
+}
+
+#[test]
+fn revoke_should_merge_description_into_metadata() {
+	new_test_ext().execute_with(|| {
+		let root_authority = generate_test_account(Role::SysMan, Some(0), None, None);
+
+		SysMan::<Test>::insert(&1u64, root_authority);
+
+		assert_ok!(SysManModule::approve_sys_man(
+			Origin::signed(1),
+			2u64,
+			str2vec(r#"{"name": "new_sys_man"}"#),
+			None
+		));
+
+		assert_ok!(SysManModule::revoke_sys_man(Origin::signed(1), 2u64, str2vec("leaked key")));
+
+		let metadata: Value =
+			serde_json::from_slice(&SysManRevoked::<Test>::get(2).unwrap().metadata).unwrap();
+
+		assert_eq!(metadata["name"], "new_sys_man");
+		assert_eq!(metadata["revoke_description"], "leaked key");
+	});
+}
+
+#[test]
+fn approve_should_validate_metadata() {
+	new_test_ext().execute_with(|| {
+		let root_authority = generate_test_account(Role::SysMan, Some(0), None, None);
+
+		SysMan::<Test>::insert(&1u64, root_authority);
+
+		// not a JSON object
+		assert_noop!(
+			SysManModule::approve_sys_man(Origin::signed(1), 2u64, str2vec(r#"["name"]"#), None),
+			Error::<Test>::MetadataNotJsonObject
+		);
+		assert_noop!(
+			SysManModule::approve_org(Origin::signed(1), 2u64, str2vec(r#"{"name": }"#), None),
+			Error::<Test>::MetadataNotJsonObject
+		);
+
+		// too long
+		let mut metadata = str2vec(r#"{"name": ""#);
+		metadata.extend(vec![b'a'; 512]);
+		metadata.extend(str2vec(r#""}"#));
+
+		assert_noop!(
+			SysManModule::approve_org(Origin::signed(1), 2u64, metadata, None),
+			Error::<Test>::MetadataTooLong
+		);
+
+		let schema = MetadataSchema {
+			required_fields: vec![
+				(str2vec("name"), JsonFieldType::String),
+				(str2vec("verified"), JsonFieldType::Bool),
+			],
+		};
+
+		assert_noop!(
+			SysManModule::set_metadata_schema(
+				Origin::signed(1),
+				OperationType::ORG,
+				schema.clone()
+			),
+			sp_runtime::DispatchError::BadOrigin
+		);
+
+		assert_ok!(SysManModule::set_metadata_schema(Origin::root(), OperationType::ORG, schema));
+
+		assert_noop!(
+			SysManModule::approve_org(Origin::signed(1), 2u64, str2vec(r#"{"name": "org"}"#), None),
+			Error::<Test>::MetadataMissingField
+		);
+		assert_noop!(
+			SysManModule::approve_org(
+				Origin::signed(1),
+				2u64,
+				str2vec(r#"{"name": "org", "verified": "yes"}"#),
+				None
+			),
+			Error::<Test>::MetadataInvalidFieldType
+		);
+		assert_ok!(SysManModule::approve_org(
+			Origin::signed(1),
+			2u64,
+			str2vec(r#"{"name": "org", "verified": true}"#),
+			None
+		));
+
+		// the org schema does not apply to system managers
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(1), 3u64, str2vec("{}"), None));
+	});
+}
+
+#[test]
+fn malformed_input_should_return_errors() {
+	new_test_ext().execute_with(|| {
+		let root_authority = generate_test_account(Role::SysMan, Some(0), None, None);
+		let no_level = generate_test_account(Role::SysMan, None, None, None);
+		let max_level = generate_test_account(Role::SysMan, Some(u8::MAX), None, None);
+
+		SysMan::<Test>::insert(&1u64, root_authority);
+		SysMan::<Test>::insert(&2u64, no_level);
+		SysMan::<Test>::insert(&3u64, max_level);
+
+		assert_noop!(
+			SysManModule::approve_sys_man(Origin::signed(2), 4u64, str2vec("{}"), None),
+			Error::<Test>::MissingLevel
+		);
+		assert_noop!(
+			SysManModule::approve_sys_man(Origin::signed(3), 4u64, str2vec("{}"), None),
+			Error::<Test>::LevelOverflow
+		);
+		assert_noop!(
+			SysManModule::revoke_sys_man(Origin::signed(1), 2u64, str2vec("no level")),
+			Error::<Test>::MissingLevel
+		);
+		assert_noop!(
+			SysManModule::revoke_sys_man(Origin::signed(1), 3u64, vec![0xff, 0xfe]),
+			Error::<Test>::DescriptionNotUtf8
+		);
+
+		assert_ok!(SysManModule::approve_org(Origin::signed(1), 5u64, str2vec("{}"), None));
+
+		assert_noop!(
+			SysManModule::revoke_org(Origin::signed(1), 5u64, vec![0xc3, 0x28]),
+			Error::<Test>::DescriptionNotUtf8
+		);
+		assert_noop!(
+			SysManModule::reinstate_org(Origin::signed(1), 5u64, vec![0xff]),
+			Error::<Test>::DescriptionNotUtf8
+		);
+		assert_noop!(
+			SysManModule::approve_org(Origin::signed(1), 6u64, vec![0xff], None),
+			Error::<Test>::MetadataNotJsonObject
+		);
+	});
+}
+
+#[test]
+fn descriptions_should_be_bounded() {
+	new_test_ext().execute_with(|| {
+		let root_authority = generate_test_account(Role::SysMan, Some(0), None, None);
+
+		SysMan::<Test>::insert(&1u64, root_authority);
+
+		let long_metadata = |len| {
+			let mut metadata = str2vec(r#"{"name":""#);
+			metadata.extend(vec![b'a'; len]);
+			metadata.extend(str2vec(r#""}"#));
+			metadata
+		};
+
+		assert_ok!(SysManModule::approve_org(Origin::signed(1), 5u64, long_metadata(450), None));
+		assert_ok!(SysManModule::approve_org(Origin::signed(1), 6u64, long_metadata(480), None));
+
+		assert_noop!(
+			SysManModule::revoke_org(Origin::signed(1), 5u64, vec![b'x'; 257]),
+			Error::<Test>::DescriptionTooLong
+		);
+		assert_noop!(
+			SysManModule::propose(
+				Origin::signed(1),
+				ProposalAction::RevokeOrg { org_id: 5u64, description: vec![b'x'; 257] }
+			),
+			Error::<Test>::DescriptionTooLong
+		);
+		assert_noop!(
+			SysManModule::propose(
+				Origin::signed(1),
+				ProposalAction::ReinstateOrg { org_id: 5u64, reason: vec![b'x'; 257] }
+			),
+			Error::<Test>::DescriptionTooLong
+		);
+		assert_noop!(
+			SysManModule::propose(
+				Origin::signed(1),
+				ProposalAction::ApproveOrg {
+					org_id: 7u64,
+					metadata: long_metadata(512),
+					expiry: None
+				}
+			),
+			Error::<Test>::MetadataTooLong
+		);
+
+		// the description is cut short to keep the metadata within `MaxMetadataLength`
+		assert_ok!(SysManModule::revoke_org(Origin::signed(1), 5u64, vec![b'x'; 256]));
+
+		let mut expected = long_metadata(450);
+		expected.truncate(expected.len() - 1);
+		expected.extend(str2vec(r#","revoke_description":""#));
+		expected.extend(vec![b'x'; 27]);
+		expected.extend(str2vec(r#""}"#));
+
+		assert_eq!(OrgRevoked::<Test>::get(5u64).unwrap().metadata, expected);
+
+		// without room for the field the metadata is kept as it was
+		assert_ok!(SysManModule::revoke_org(Origin::signed(1), 6u64, vec![b'x'; 10]));
+
+		assert_eq!(OrgRevoked::<Test>::get(6u64).unwrap().metadata, long_metadata(480));
+	});
+}
+
+// Property test: random call sequences must never make a dispatchable panic and must keep the
+// parent/children links of active system managers consistent.
+
+struct Rng(u64);
+
+impl Rng {
+	fn next(&mut self) -> u64 {
+		// xorshift64
+		self.0 ^= self.0 << 13;
+		self.0 ^= self.0 >> 7;
+		self.0 ^= self.0 << 17;
+		self.0
+	}
+
+	fn below(&mut self, n: u64) -> u64 {
+		self.next() % n
+	}
+
+	fn account(&mut self) -> u64 {
+		self.below(10)
+	}
+
+	fn bytes(&mut self) -> Vec<u8> {
+		match self.below(5) {
+			0 => str2vec("{}"),
+			1 => str2vec(r#"{"name": "fuzz"}"#),
+			2 => str2vec("not json"),
+			3 => vec![0xff, 0xfe, 0xfd],
+			_ => (0..self.below(600)).map(|_| self.next() as u8).collect(),
+		}
+	}
+
+	fn block(&mut self) -> Option<u64> {
+		match self.below(3) {
+			0 => None,
+			_ => Some(self.below(20)),
+		}
+	}
+}
+
+fn dispatch_random_call(rng: &mut Rng) {
+	let origin = Origin::signed(rng.account());
+
+	let _ = match rng.below(25) {
+		0 | 1 => SysManModule::approve_sys_man(origin, rng.account(), rng.bytes(), rng.block()),
+		2 | 3 => SysManModule::approve_org(origin, rng.account(), rng.bytes(), rng.block()),
+		4 => SysManModule::revoke_sys_man(origin, rng.account(), rng.bytes()),
+		5 => SysManModule::revoke_sys_man_cascade(origin, rng.account(), rng.bytes()),
+		6 => SysManModule::continue_cascade_revocation(origin, rng.account()),
+		7 => SysManModule::revoke_org(origin, rng.account(), rng.bytes()),
+		8 => SysManModule::reinstate_sys_man(origin, rng.account(), rng.bytes()),
+		9 => SysManModule::reinstate_org(origin, rng.account(), rng.bytes()),
+		10 => SysManModule::suspend_sys_man(origin, rng.account(), rng.block()),
+		11 => SysManModule::resume_sys_man(origin, rng.account()),
+		12 => SysManModule::suspend_org(origin, rng.account(), rng.block()),
+		13 => SysManModule::resume_org(origin, rng.account()),
+		14 => {
+			let schema =
+				MetadataSchema { required_fields: vec![(rng.bytes(), JsonFieldType::String)] };
+
+			SysManModule::set_metadata_schema(Origin::root(), OperationType::SYS, schema)
+		},
+		15 => {
+			let action = match rng.below(5) {
+				0 => ProposalAction::ApproveSysMan {
+					sys_man_id: rng.account(),
+					metadata: rng.bytes(),
+					expiry: rng.block(),
+				},
+				1 => ProposalAction::ApproveOrg {
+					org_id: rng.account(),
+					metadata: rng.bytes(),
+					expiry: rng.block(),
+				},
+				2 => ProposalAction::RevokeOrg { org_id: rng.account(), description: rng.bytes() },
+				3 => ProposalAction::ReinstateSysMan {
+					sys_man_id: rng.account(),
+					reason: rng.bytes(),
+				},
+				_ => ProposalAction::ReinstateOrg { org_id: rng.account(), reason: rng.bytes() },
+			};
+
+			SysManModule::propose(origin, action)
+		},
+		16 => SysManModule::vote(origin, rng.below(4) as u32),
+		17 => SysManModule::execute_proposal(origin, rng.below(4) as u32),
+		18 => SysManModule::set_approval_threshold(Origin::root(), rng.below(3) as u32),
+		19 => SysManModule::renew_sys_man(origin, rng.account(), rng.block()),
+		20 => SysManModule::renew_org(origin, rng.account(), rng.block()),
+		21 => match rng.below(2) {
+			0 => SysManModule::rotate_sys_man_key(origin, rng.account()),
+			_ => SysManModule::rotate_org_key(origin, rng.account()),
+		},
+		22 => {
+			let role = match rng.below(3) {
+				0 => OrgRole::Admin,
+				1 => OrgRole::Issuer,
+				_ => OrgRole::Verifier,
+			};
+
+			SysManModule::add_org_member(origin, rng.account(), rng.account(), role)
+		},
+		23 => SysManModule::remove_org_member(origin, rng.account(), rng.account()),
+		_ => {
+			let block = System::block_number() + 1;
+
+			System::set_block_number(block);
+			SysManModule::on_initialize(block);
+
+			Ok(().into())
+		},
+	};
+}
+
+fn assert_hierarchy_is_consistent() {
+	for (id, sys_man) in SysMan::<Test>::iter() {
+		if let Some(parent) = sys_man.parent.and_then(|parent| SysMan::<Test>::get(parent)) {
+			assert!(parent.children.unwrap_or_default().contains(&id));
+		}
+	}
+
+	assert_eq!(SysManModule::sys_man_cnt(), SysMan::<Test>::iter().count() as u64);
+	assert_eq!(SysManModule::org_cnt(), Org::<Test>::iter().count() as u64);
+
+	for (org_id, org) in Org::<Test>::iter() {
+		if let Some(approver) = org.parent {
+			assert!(OrgsBySysMan::<Test>::contains_key(&approver, &org_id));
+		}
+	}
+
+	for (approver, org_id, _) in OrgsBySysMan::<Test>::iter() {
+		assert_eq!(Org::<Test>::get(&org_id).and_then(|org| org.parent), Some(approver));
+	}
+
+	for (approver, cnt) in OrgCntBySysMan::<Test>::iter() {
+		assert_eq!(OrgsBySysMan::<Test>::iter_prefix(&approver).count() as u32, cnt);
+	}
+
+	for (org_id, cnt) in OrgMemberCnt::<Test>::iter() {
+		assert_eq!(OrgMembers::<Test>::iter_prefix(&org_id).count() as u32, cnt);
+	}
+}
+
+#[test]
+fn random_call_sequences_should_not_panic() {
+	for seed in 1..=32u64 {
+		new_test_ext().execute_with(|| {
+			let mut rng = Rng(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15));
+
+			SysMan::<Test>::insert(&0u64, generate_test_account(Role::SysMan, Some(0), None, None));
+			SysMan::<Test>::insert(&9u64, generate_test_account(Role::SysMan, None, None, None));
+			SysManCnt::<Test>::put(2);
+
+			for _ in 0..500 {
+				dispatch_random_call(&mut rng);
+				assert_hierarchy_is_consistent();
+			}
+		});
+	}
+}
+
+#[test]
+fn approve_sys_man_should_respect_hierarchy_limits() {
+	new_test_ext().execute_with(|| {
+		let root_authority = generate_test_account(Role::SysMan, Some(0), None, None);
+
+		SysMan::<Test>::insert(&1u64, root_authority);
+
+		assert_eq!(SysManModule::max_depth(), 4);
+		assert_eq!(SysManModule::max_children(), 3);
+
+		// fan-out
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(1), 2u64, str2vec("{}"), None));
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(1), 3u64, str2vec("{}"), None));
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(1), 4u64, str2vec("{}"), None));
+		assert_noop!(
+			SysManModule::approve_sys_man(Origin::signed(1), 5u64, str2vec("{}"), None),
+			Error::<Test>::TooManyChildren
+		);
+
+		// depth
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(2), 5u64, str2vec("{}"), None));
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(5), 6u64, str2vec("{}"), None));
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(6), 7u64, str2vec("{}"), None));
+		assert_eq!(SysMan::<Test>::get(7).unwrap().level, Some(4));
+		assert_noop!(
+			SysManModule::approve_sys_man(Origin::signed(7), 8u64, str2vec("{}"), None),
+			Error::<Test>::MaxDepthExceeded
+		);
+
+		assert_noop!(
+			SysManModule::set_hierarchy_limits(Origin::signed(1), 5, 4),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(
+			SysManModule::set_hierarchy_limits(Origin::root(), 5, 6),
+			Error::<Test>::HierarchyLimitTooHigh
+		);
+		assert_ok!(SysManModule::set_hierarchy_limits(Origin::root(), 5, 4));
+
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(1), 8u64, str2vec("{}"), None));
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(7), 9u64, str2vec("{}"), None));
+	});
+}
+
+fn setup_consortium() {
+	// two root authorities endorsing each other's proposals
+	SysMan::<Test>::insert(&1u64, generate_test_account(Role::SysMan, Some(0), None, None));
+	SysMan::<Test>::insert(&2u64, generate_test_account(Role::SysMan, Some(0), None, None));
+
+	assert_ok!(SysManModule::set_approval_threshold(Origin::root(), 2));
+}
+
+#[test]
+fn high_privilege_operations_should_require_proposal() {
+	new_test_ext().execute_with(|| {
+		setup_consortium();
+
+		assert_noop!(
+			SysManModule::approve_org(Origin::signed(1), 10u64, str2vec("{}"), None),
+			Error::<Test>::ProposalRequired
+		);
+		assert_noop!(
+			SysManModule::revoke_org(Origin::signed(1), 10u64, str2vec("reason")),
+			Error::<Test>::ProposalRequired
+		);
+		assert_noop!(
+			SysManModule::approve_sys_man(Origin::signed(1), 10u64, str2vec("{}"), None),
+			Error::<Test>::ProposalRequired
+		);
+
+		// lower level managers are not affected
+		SysMan::<Test>::insert(&3u64, generate_test_account(Role::SysMan, Some(1), None, None));
+
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(3), 10u64, str2vec("{}"), None));
+	});
+}
+
+#[test]
+fn proposal_should_execute_with_enough_approvals() {
+	new_test_ext().execute_with(|| {
+		setup_consortium();
+
+		let action = ProposalAction::ApproveOrg {
+			org_id: 10u64,
+			metadata: str2vec(r#"{"name": "org"}"#),
+			expiry: None,
+		};
+
+		assert_ok!(SysManModule::propose(Origin::signed(1), action));
+
+		assert_eq!(SysManModule::proposal(0).unwrap().approvals, vec![1u64]);
+
+		assert_noop!(
+			SysManModule::execute_proposal(Origin::signed(1), 0),
+			Error::<Test>::InsufficientApprovals
+		);
+		assert_noop!(SysManModule::vote(Origin::signed(1), 0), Error::<Test>::AlreadyVoted);
+		assert_noop!(SysManModule::vote(Origin::signed(10), 0), Error::<Test>::SysManNotExist);
+
+		assert_ok!(SysManModule::vote(Origin::signed(2), 0));
+
+		System::assert_last_event(mock::Event::SysManModule(crate::Event::Voted {
+			proposal_id: 0,
+			voter: 2u64,
+			approvals: 2,
+		}));
+
+		assert_ok!(SysManModule::execute_proposal(Origin::signed(10), 0));
+
+		assert!(Org::<Test>::contains_key(&10u64));
+		assert!(SysManModule::proposal(0).is_none());
+
+		// revoking needs endorsements as well, and is proposed by the approver of the org
+		let action = ProposalAction::RevokeOrg { org_id: 10u64, description: str2vec("reason") };
+
+		assert_ok!(SysManModule::propose(Origin::signed(1), action));
+		assert_ok!(SysManModule::vote(Origin::signed(2), 1));
+		assert_ok!(SysManModule::execute_proposal(Origin::signed(2), 1));
+
+		assert!(OrgRevoked::<Test>::contains_key(&10u64));
+
+		// level-1 managers are appointed by the proposer
+		let action = ProposalAction::ApproveSysMan {
+			sys_man_id: 11u64,
+			metadata: str2vec("{}"),
+			expiry: None,
+		};
+
+		assert_ok!(SysManModule::propose(Origin::signed(2), action));
+		assert_ok!(SysManModule::vote(Origin::signed(1), 2));
+		assert_ok!(SysManModule::execute_proposal(Origin::signed(2), 2));
+
+		assert_eq!(SysMan::<Test>::get(11).unwrap().parent, Some(2u64));
+		assert_eq!(SysMan::<Test>::get(11).unwrap().level, Some(1));
+	});
+}
+
+#[test]
+fn appointees_should_not_endorse_for_their_appointer() {
+	new_test_ext().execute_with(|| {
+		setup_consortium();
+
+		// 3 sits below root 1 and appoints 4 without any proposal
+		SysMan::<Test>::insert(
+			&1u64,
+			generate_test_account(Role::SysMan, Some(0), None, Some(vec![3])),
+		);
+		SysMan::<Test>::insert(&3u64, generate_test_account(Role::SysMan, Some(1), Some(1), None));
+
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(3), 4u64, str2vec("{}"), None));
+
+		let action =
+			ProposalAction::ApproveOrg { org_id: 10u64, metadata: str2vec("{}"), expiry: None };
+
+		// managers further down can neither propose nor vote
+		assert_noop!(
+			SysManModule::propose(Origin::signed(4), action.clone()),
+			Error::<Test>::NotEligibleEndorser
+		);
+
+		assert_ok!(SysManModule::propose(Origin::signed(1), action.clone()));
+
+		assert_noop!(SysManModule::vote(Origin::signed(4), 0), Error::<Test>::NotEligibleEndorser);
+		assert_noop!(
+			SysManModule::vote(Origin::signed(3), 0),
+			Error::<Test>::ConflictingEndorsement
+		);
+
+		// an appointer seconding its own appointee does not reach the threshold either
+		assert_ok!(SysManModule::propose(Origin::signed(3), action));
+		assert_ok!(SysManModule::vote(Origin::signed(1), 1));
+
+		assert_noop!(
+			SysManModule::execute_proposal(Origin::signed(1), 1),
+			Error::<Test>::InsufficientApprovals
+		);
+
+		assert_ok!(SysManModule::vote(Origin::signed(2), 1));
+		assert_ok!(SysManModule::execute_proposal(Origin::signed(1), 1));
+
+		assert!(Org::<Test>::contains_key(&10u64));
+	});
+}
+
+#[test]
+fn reinstatement_should_require_proposal() {
+	new_test_ext().execute_with(|| {
+		setup_consortium();
+
+		SysMan::<Test>::insert(
+			&1u64,
+			generate_test_account(Role::SysMan, Some(0), None, Some(vec![3])),
+		);
+		SysMan::<Test>::insert(&3u64, generate_test_account(Role::SysMan, Some(1), Some(1), None));
+		Org::<Test>::insert(&10u64, generate_test_account(Role::Organization, None, Some(1), None));
+		OrgsBySysMan::<Test>::insert(&1u64, &10u64, ());
+
+		assert_ok!(SysManModule::revoke_sys_man(Origin::signed(1), 3u64, str2vec("reason")));
+
+		let action = ProposalAction::RevokeOrg { org_id: 10u64, description: str2vec("reason") };
+
+		assert_ok!(SysManModule::propose(Origin::signed(1), action));
+		assert_ok!(SysManModule::vote(Origin::signed(2), 0));
+		assert_ok!(SysManModule::execute_proposal(Origin::signed(1), 0));
+
+		// a single root authority can no longer undo what the consortium agreed on
+		assert_noop!(
+			SysManModule::reinstate_sys_man(Origin::signed(1), 3u64, str2vec("mistake")),
+			Error::<Test>::ProposalRequired
+		);
+		assert_noop!(
+			SysManModule::reinstate_org(Origin::signed(1), 10u64, str2vec("mistake")),
+			Error::<Test>::ProposalRequired
+		);
+
+		let action =
+			ProposalAction::ReinstateSysMan { sys_man_id: 3u64, reason: str2vec("mistake") };
+
+		assert_ok!(SysManModule::propose(Origin::signed(1), action));
+		assert_ok!(SysManModule::vote(Origin::signed(2), 1));
+		assert_ok!(SysManModule::execute_proposal(Origin::signed(1), 1));
+
+		assert_eq!(SysMan::<Test>::get(3).unwrap().parent, Some(1u64));
+		assert_eq!(SysManModule::sys_man_reinstated(&3u64).unwrap().reinstated_by, 1u64);
+
+		let action = ProposalAction::ReinstateOrg { org_id: 10u64, reason: str2vec("mistake") };
+
+		assert_ok!(SysManModule::propose(Origin::signed(1), action));
+		assert_ok!(SysManModule::vote(Origin::signed(2), 2));
+		assert_ok!(SysManModule::execute_proposal(Origin::signed(1), 2));
+
+		assert_eq!(Org::<Test>::get(10).unwrap().status, Status::Active);
+		assert_eq!(SysManModule::org_reinstated(&10u64).unwrap().reinstated_by, 1u64);
+	});
+}
+
+#[test]
+fn suspended_endorsements_should_not_count() {
+	new_test_ext().execute_with(|| {
+		setup_consortium();
+
+		let action =
+			ProposalAction::ApproveOrg { org_id: 10u64, metadata: str2vec("{}"), expiry: None };
+
+		assert_ok!(SysManModule::propose(Origin::signed(1), action));
+		assert_ok!(SysManModule::vote(Origin::signed(2), 0));
+
+		SysMan::<Test>::mutate(&2u64, |val| val.as_mut().unwrap().status = Status::Deactivated);
+
+		assert_noop!(
+			SysManModule::execute_proposal(Origin::signed(1), 0),
+			Error::<Test>::InsufficientApprovals
+		);
+	});
+}
+
+#[test]
+fn stale_proposal_should_expire() {
+	new_test_ext().execute_with(|| {
+		setup_consortium();
+
+		let action =
+			ProposalAction::ApproveOrg { org_id: 10u64, metadata: str2vec("{}"), expiry: None };
+
+		assert_ok!(SysManModule::propose(Origin::signed(1), action));
+
+		assert_eq!(SysManModule::proposal(0).unwrap().expiry, 11);
+
+		System::set_block_number(11);
+
+		assert_noop!(SysManModule::vote(Origin::signed(2), 0), Error::<Test>::ProposalExpired);
+
+		SysManModule::on_initialize(11);
+
+		assert!(SysManModule::proposal(0).is_none());
+
+		System::assert_last_event(mock::Event::SysManModule(crate::Event::ProposalExpired {
+			proposal_id: 0,
+		}));
+
+		assert_noop!(SysManModule::vote(Origin::signed(2), 0), Error::<Test>::ProposalNotExist);
+	});
+}
+
+#[test]
+fn mandate_should_expire() {
+	new_test_ext().execute_with(|| {
+		let root_authority = generate_test_account(Role::SysMan, Some(0), None, None);
+
+		SysMan::<Test>::insert(&1u64, root_authority);
+
+		assert_noop!(
+			SysManModule::approve_sys_man(Origin::signed(1), 2u64, str2vec("{}"), Some(1)),
+			Error::<Test>::InvalidExpiry
+		);
+
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(1), 2u64, str2vec("{}"), Some(5)));
+		assert_ok!(SysManModule::approve_org(Origin::signed(1), 3u64, str2vec("{}"), Some(5)));
+
+		assert_eq!(SysManModule::mandate_expiry(&2u64, OperationType::SYS), Some(5));
+		assert!(SysManModule::is_active(&3u64, OperationType::ORG));
+
+		System::set_block_number(5);
+		SysManModule::on_initialize(5);
+
+		System::assert_has_event(mock::Event::SysManModule(crate::Event::MandateExpired {
+			target_id: 2u64,
+			op_type: OperationType::SYS,
+		}));
+		System::assert_has_event(mock::Event::SysManModule(crate::Event::MandateExpired {
+			target_id: 3u64,
+			op_type: OperationType::ORG,
+		}));
+
+		// expired accounts are treated as inactive
+		assert_eq!(false, SysManModule::is_active(&3u64, OperationType::ORG));
+		assert_noop!(
+			SysManModule::approve_sys_man(Origin::signed(2), 4u64, str2vec("{}"), None),
+			Error::<Test>::MandateExpired
+		);
+
+		assert_noop!(
+			SysManModule::renew_sys_man(Origin::signed(1), 2u64, Some(5)),
+			Error::<Test>::InvalidExpiry
+		);
+		assert_ok!(SysManModule::renew_sys_man(Origin::signed(1), 2u64, Some(10)));
+		assert_ok!(SysManModule::renew_org(Origin::signed(1), 3u64, None));
+
+		assert!(SysManModule::is_active(&3u64, OperationType::ORG));
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(2), 4u64, str2vec("{}"), None));
+
+		// renewing to the same block, or away and back, expires the mandate once
+		assert_ok!(SysManModule::renew_sys_man(Origin::signed(1), 2u64, Some(10)));
+		assert_ok!(SysManModule::renew_sys_man(Origin::signed(1), 2u64, Some(8)));
+		assert_ok!(SysManModule::renew_sys_man(Origin::signed(1), 2u64, Some(10)));
+
+		assert_eq!(SysManModule::mandate_expiries(10).len(), 2);
+
+		// the expiry scheduled for the old mandate is ignored
+		System::set_block_number(10);
+		System::reset_events();
+		SysManModule::on_initialize(10);
+
+		assert_eq!(System::events().len(), 1);
+		assert_eq!(false, SysManModule::is_active(&2u64, OperationType::SYS));
+	});
+}
+
+#[test]
+fn rotate_sys_man_key_should_work() {
+	new_test_ext().execute_with(|| {
+		let root_authority = generate_test_account(Role::SysMan, Some(0), None, None);
+
+		SysMan::<Test>::insert(&1u64, root_authority);
+
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(1), 2u64, str2vec("{}"), Some(5)));
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(1), 3u64, str2vec("{}"), None));
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(2), 4u64, str2vec("{}"), None));
+		assert_ok!(SysManModule::suspend_sys_man(Origin::signed(1), 2u64, Some(3)));
+
+		assert_noop!(
+			SysManModule::rotate_sys_man_key(Origin::signed(2), 3u64),
+			Error::<Test>::AlreadyRegistered
+		);
+		assert_noop!(
+			SysManModule::rotate_sys_man_key(Origin::signed(9), 10u64),
+			Error::<Test>::SysManNotExist
+		);
+
+		assert_ok!(SysManModule::rotate_sys_man_key(Origin::signed(2), 5u64));
+
+		System::assert_last_event(mock::Event::SysManModule(crate::Event::KeyRotated {
+			old_id: 2u64,
+			new_id: 5u64,
+			op_type: OperationType::SYS,
+		}));
+
+		assert_eq!(SysManModule::sys_man(&2u64), None);
+		assert_eq!(SysManModule::rotated_to(&2u64, OperationType::SYS), Some(5u64));
+		assert_eq!(SysManModule::rotated_from(&5u64, OperationType::SYS), Some(2u64));
+
+		// the hierarchy is re-linked to the new key without reordering
+		assert_eq!(SysManModule::sys_man(&1u64).unwrap().children, Some(vec![5u64, 3u64]));
+		assert_eq!(SysManModule::sys_man(&4u64).unwrap().parent, Some(5u64));
+		assert_eq!(SysManModule::sys_man(&5u64).unwrap().children, Some(vec![4u64]));
+
+		// the suspension and mandate follow the new key
+		assert_eq!(SysManModule::mandate_expiry(&5u64, OperationType::SYS), Some(5));
+
+		System::set_block_number(3);
+		SysManModule::on_initialize(3);
+
+		assert_eq!(SysManModule::sys_man(&5u64).unwrap().status, Status::Active);
+
+		System::set_block_number(5);
+		SysManModule::on_initialize(5);
+
+		System::assert_has_event(mock::Event::SysManModule(crate::Event::MandateExpired {
+			target_id: 5u64,
+			op_type: OperationType::SYS,
+		}));
+
+		// retired keys can not be taken again
+		assert_noop!(
+			SysManModule::rotate_sys_man_key(Origin::signed(3), 2u64),
+			Error::<Test>::KeyAlreadyUsed
+		);
+	});
+}
+
+#[test]
+fn revoked_managers_should_follow_rotated_superiors() {
+	new_test_ext().execute_with(|| {
+		let root_authority = generate_test_account(Role::SysMan, Some(0), None, None);
+
+		SysMan::<Test>::insert(&1u64, root_authority);
+
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(1), 2u64, str2vec("{}"), None));
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(2), 3u64, str2vec("{}"), None));
+		assert_ok!(SysManModule::approve_org(Origin::signed(3), 10u64, str2vec("{}"), None));
+
+		assert_ok!(SysManModule::revoke_sys_man(Origin::signed(2), 3u64, str2vec("reason")));
+		assert_ok!(SysManModule::rotate_sys_man_key(Origin::signed(2), 5u64));
+
+		// the revoked manager still points to the old key of its approver
+		assert_eq!(SysManModule::sys_man_revoked(&3u64).unwrap().parent, Some(2u64));
+		assert_eq!(SysManModule::ancestors(&3u64), vec![5u64, 1u64]);
+
+		assert_ok!(SysManModule::revoke_org(Origin::signed(5), 10u64, str2vec("reason")));
+		assert_ok!(SysManModule::reinstate_org(Origin::signed(1), 10u64, str2vec("reason")));
+
+		// the reinstated manager is re-attached to the new key of its approver
+		assert_ok!(SysManModule::reinstate_sys_man(Origin::signed(5), 3u64, str2vec("reason")));
+
+		assert_eq!(SysManModule::sys_man(&3u64).unwrap().parent, Some(5u64));
+		assert_eq!(SysManModule::children_of(&5u64), vec![3u64]);
+	});
+}
+
+#[test]
+fn approve_org_should_respect_orgs_per_sys_man() {
+	new_test_ext().execute_with(|| {
+		SysMan::<Test>::insert(&1u64, generate_test_account(Role::SysMan, Some(0), None, None));
+
+		for org_id in 10u64..15 {
+			assert_ok!(SysManModule::approve_org(Origin::signed(1), org_id, str2vec("{}"), None));
+		}
+
+		assert_eq!(SysManModule::org_cnt_by_sys_man(&1u64), 5);
+		assert_noop!(
+			SysManModule::approve_org(Origin::signed(1), 15u64, str2vec("{}"), None),
+			Error::<Test>::TooManyOrgs
+		);
+
+		// revoking frees a slot, and the count follows a key rotation
+		assert_ok!(SysManModule::revoke_org(Origin::signed(1), 10u64, str2vec("reason")));
+		assert_ok!(SysManModule::rotate_sys_man_key(Origin::signed(1), 2u64));
+
+		assert_eq!(SysManModule::org_cnt_by_sys_man(&1u64), 0);
+		assert_eq!(SysManModule::org_cnt_by_sys_man(&2u64), 4);
+
+		assert_ok!(SysManModule::approve_org(Origin::signed(2), 15u64, str2vec("{}"), None));
+		assert_noop!(
+			SysManModule::reinstate_org(Origin::signed(2), 10u64, str2vec("reason")),
+			Error::<Test>::TooManyOrgs
+		);
+
+		assert_hierarchy_is_consistent();
+	});
+}
+
+#[test]
+fn proposals_should_follow_rotated_keys() {
+	new_test_ext().execute_with(|| {
+		setup_consortium();
+
+		let action =
+			ProposalAction::ApproveOrg { org_id: 10u64, metadata: str2vec("{}"), expiry: None };
+
+		assert_ok!(SysManModule::propose(Origin::signed(1), action));
+		assert_ok!(SysManModule::rotate_sys_man_key(Origin::signed(1), 3u64));
+
+		// the endorsement of the old key is kept for the new one
+		assert_noop!(SysManModule::vote(Origin::signed(3), 0), Error::<Test>::AlreadyVoted);
+
+		assert_ok!(SysManModule::vote(Origin::signed(2), 0));
+		assert_ok!(SysManModule::rotate_sys_man_key(Origin::signed(2), 4u64));
+		assert_ok!(SysManModule::execute_proposal(Origin::signed(4), 0));
+
+		// the organization is approved by the new key of the proposer
+		assert_eq!(SysManModule::org(&10u64).unwrap().parent, Some(3u64));
+		assert_eq!(SysManModule::org_cnt_by_sys_man(&3u64), 1);
+	});
+}
+
+#[test]
+fn rotate_org_key_should_work() {
+	new_test_ext().execute_with(|| {
+		let root_authority = generate_test_account(Role::SysMan, Some(0), None, None);
+
+		SysMan::<Test>::insert(&1u64, root_authority);
+
+		assert_ok!(SysManModule::approve_org(Origin::signed(1), 2u64, str2vec("{}"), None));
+		assert_ok!(SysManModule::approve_org(Origin::signed(1), 3u64, str2vec("{}"), None));
+
+		assert_noop!(
+			SysManModule::rotate_org_key(Origin::signed(2), 3u64),
+			Error::<Test>::AlreadyRegistered
+		);
+
+		assert_ok!(SysManModule::rotate_org_key(Origin::signed(2), 4u64));
+
+		assert_eq!(SysManModule::org(&2u64), None);
+		assert!(SysManModule::is_active(&4u64, OperationType::ORG));
+		assert_eq!(SysManModule::rotated_to(&2u64, OperationType::ORG), Some(4u64));
+	});
+}
+
+#[test]
+fn org_members_should_work() {
+	new_test_ext().execute_with(|| {
+		let root_authority = generate_test_account(Role::SysMan, Some(0), None, None);
+
+		SysMan::<Test>::insert(&1u64, root_authority);
+
+		assert_ok!(SysManModule::approve_org(Origin::signed(1), 2u64, str2vec("{}"), None));
+
+		assert_noop!(
+			SysManModule::add_org_member(Origin::signed(3), 2u64, 4u64, OrgRole::Issuer),
+			Error::<Test>::NotOrgAdmin
+		);
+
+		assert_ok!(SysManModule::add_org_member(Origin::signed(2), 2u64, 3u64, OrgRole::Admin));
+
+		System::assert_last_event(mock::Event::SysManModule(crate::Event::OrgMemberAdded {
+			org_id: 2u64,
+			member: 3u64,
+			role: OrgRole::Admin,
+		}));
+
+		// admins can manage the staff of the organization
+		assert_ok!(SysManModule::add_org_member(Origin::signed(3), 2u64, 4u64, OrgRole::Issuer));
+		assert_ok!(SysManModule::add_org_member(Origin::signed(3), 2u64, 5u64, OrgRole::Verifier));
+
+		assert_noop!(
+			SysManModule::add_org_member(Origin::signed(4), 2u64, 6u64, OrgRole::Issuer),
+			Error::<Test>::NotOrgAdmin
+		);
+		assert_noop!(
+			SysManModule::add_org_member(Origin::signed(2), 2u64, 6u64, OrgRole::Issuer),
+			Error::<Test>::TooManyMembers
+		);
+
+		// changing the role of a member does not count against the limit
+		assert_ok!(SysManModule::add_org_member(Origin::signed(2), 2u64, 5u64, OrgRole::Issuer));
+		assert_eq!(SysManModule::org_member_cnt(&2u64), 3);
+
+		assert_eq!(SysManModule::member_role(&2u64, &2u64), Some(OrgRole::Admin));
+		assert_eq!(SysManModule::member_role(&2u64, &4u64), Some(OrgRole::Issuer));
+		assert!(SysManModule::is_member(&2u64, &5u64));
+		assert_eq!(false, SysManModule::is_member(&2u64, &6u64));
+
+		// members can leave on their own
+		assert_ok!(SysManModule::remove_org_member(Origin::signed(5), 2u64, 5u64));
+		assert_noop!(
+			SysManModule::remove_org_member(Origin::signed(4), 2u64, 3u64),
+			Error::<Test>::NotOrgAdmin
+		);
+		assert_noop!(
+			SysManModule::remove_org_member(Origin::signed(2), 2u64, 5u64),
+			Error::<Test>::NotOrgMember
+		);
+		assert_eq!(SysManModule::org_member_cnt(&2u64), 2);
+
+		// the staff follows the organization to its new key
+		assert_ok!(SysManModule::rotate_org_key(Origin::signed(2), 7u64));
+
+		assert_eq!(SysManModule::member_role(&7u64, &4u64), Some(OrgRole::Issuer));
+		assert_eq!(SysManModule::member_role(&2u64, &4u64), None);
+		assert_eq!(SysManModule::org_member_cnt(&7u64), 2);
+
+		// members of a suspended organization can not act for it
+		assert_ok!(SysManModule::suspend_org(Origin::signed(1), 7u64, Some(5)));
+
+		assert_eq!(SysManModule::member_role(&7u64, &4u64), None);
+		assert_noop!(
+			SysManModule::add_org_member(Origin::signed(7), 7u64, 6u64, OrgRole::Issuer),
+			Error::<Test>::AccountSuspended
+		);
+	});
+}
+
+#[test]
+fn only_approver_or_ancestor_should_revoke_org() {
+	new_test_ext().execute_with(|| {
+		let root_authority = generate_test_account(Role::SysMan, Some(0), None, None);
+
+		SysMan::<Test>::insert(&1u64, root_authority);
+
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(1), 2u64, str2vec("{}"), None));
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(1), 3u64, str2vec("{}"), None));
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(2), 4u64, str2vec("{}"), None));
+
+		assert_ok!(SysManModule::approve_org(Origin::signed(4), 10u64, str2vec("{}"), None));
+		assert_ok!(SysManModule::approve_org(Origin::signed(4), 11u64, str2vec("{}"), None));
+
+		assert_eq!(SysManModule::org(&10u64).unwrap().parent, Some(4u64));
+		assert_eq!(SysManModule::orgs_by_sys_man(&4u64, &10u64), Some(()));
+
+		// a manager in another branch can not revoke the org
+		assert_noop!(
+			SysManModule::revoke_org(Origin::signed(3), 10u64, str2vec("reason")),
+			Error::<Test>::NotOrgApprover
+		);
+
+		assert_ok!(SysManModule::revoke_org(Origin::signed(4), 10u64, str2vec("reason")));
+		assert_eq!(SysManModule::orgs_by_sys_man(&4u64, &10u64), None);
+
+		// the approver rotating its key keeps its orgs
+		assert_ok!(SysManModule::rotate_sys_man_key(Origin::signed(4), 5u64));
+
+		assert_eq!(SysManModule::org(&11u64).unwrap().parent, Some(5u64));
+		assert_eq!(SysManModule::orgs_by_sys_man(&5u64, &11u64), Some(()));
+
+		// ancestors can still revoke after the approver has been revoked
+		assert_ok!(SysManModule::revoke_sys_man(Origin::signed(2), 5u64, str2vec("reason")));
+		assert_ok!(SysManModule::revoke_org(Origin::signed(1), 11u64, str2vec("reason")));
+
+		// a manager above the gone approver takes over the org it reinstates
+		assert_noop!(
+			SysManModule::reinstate_org(Origin::signed(3), 11u64, str2vec("reason")),
+			Error::<Test>::NotOrgApprover
+		);
+		assert_ok!(SysManModule::reinstate_org(Origin::signed(2), 11u64, str2vec("reason")));
+
+		assert_eq!(SysManModule::org(&11u64).unwrap().parent, Some(2u64));
+		assert_eq!(SysManModule::orgs_by_sys_man(&2u64, &11u64), Some(()));
+
+		// managers in other branches can not suspend, resume or renew it either
+		assert_noop!(
+			SysManModule::suspend_org(Origin::signed(3), 11u64, None),
+			Error::<Test>::NotOrgApprover
+		);
+		assert_ok!(SysManModule::suspend_org(Origin::signed(2), 11u64, None));
+		assert_noop!(
+			SysManModule::resume_org(Origin::signed(3), 11u64),
+			Error::<Test>::NotOrgApprover
+		);
+		assert_ok!(SysManModule::resume_org(Origin::signed(1), 11u64));
+		assert_noop!(
+			SysManModule::renew_org(Origin::signed(3), 11u64, None),
+			Error::<Test>::NotOrgApprover
+		);
+		assert_ok!(SysManModule::renew_org(Origin::signed(2), 11u64, Some(10)));
+	});
+}
+
+#[test]
+fn governance_actions_should_be_audited() {
+	new_test_ext().execute_with(|| {
+		let root_authority = generate_test_account(Role::SysMan, Some(0), None, None);
+
+		SysMan::<Test>::insert(&1u64, root_authority);
+		SysManCnt::<Test>::put(1);
+
+		let metadata = str2vec(r#"{"name": "manager"}"#);
+
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(1), 2u64, metadata.clone(), None));
+		assert_ok!(SysManModule::approve_org(Origin::signed(2), 3u64, str2vec("{}"), None));
+
+		System::set_block_number(2);
+
+		assert_ok!(SysManModule::suspend_sys_man(Origin::signed(1), 2u64, None));
+		assert_ok!(SysManModule::set_approval_threshold(Origin::root(), 1));
+		assert_ok!(SysManModule::revoke_org(Origin::signed(1), 3u64, str2vec("reason")));
+
+		assert_eq!(SysManModule::sys_man_cnt(), 2);
+		assert_eq!(SysManModule::org_cnt(), 0);
+		assert_eq!(SysManModule::audit_log_cnt(), 5);
+
+		assert_eq!(
+			SysManModule::audit_log(0),
+			Some(AuditEntry {
+				actor: Some(1u64),
+				action: AuditAction::Approve(OperationType::SYS),
+				target: Some(2u64),
+				block: 1,
+				timestamp: 6_000,
+				description_hash: Some(BlakeTwo256::hash(&metadata)),
+			})
+		);
+
+		let entry = SysManModule::audit_log(3).unwrap();
+
+		assert_eq!(entry.actor, None);
+		assert_eq!(entry.action, AuditAction::SetApprovalThreshold(1));
+		assert_eq!(entry.timestamp, 12_000);
+
+		// pages are capped at `MaxPageSize`
+		assert_eq!(SysManModule::audit_log_page(0, 10).len(), 3);
+
+		// pages stop at the end of the log
+		let page = SysManModule::audit_log_page(3, 10);
+
+		assert_eq!(page.iter().map(|(id, _)| *id).collect::<Vec<_>>(), vec![3, 4]);
+		assert_eq!(page[1].1.action, AuditAction::Revoke(OperationType::ORG));
+
+		// every entry is indexed under its actor and its target
+		let page = SysManModule::audit_log_page_of(&2u64, 0, 10);
+
+		assert_eq!(page.iter().map(|(id, _)| *id).collect::<Vec<_>>(), vec![0, 1, 2]);
+		assert_eq!(SysManModule::audit_log_page_of(&2u64, 1, 1)[0].0, 1);
+		assert_eq!(SysManModule::audit_log_page_of(&3u64, 0, 10).len(), 2);
+	});
+}
+
+#[test]
+fn hierarchy_queries_should_work() {
+	new_test_ext().execute_with(|| {
+		let root_authority = generate_test_account(Role::SysMan, Some(0), None, None);
+
+		SysMan::<Test>::insert(&1u64, root_authority);
+
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(1), 2u64, str2vec("{}"), None));
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(1), 3u64, str2vec("{}"), None));
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(2), 4u64, str2vec("{}"), None));
+		assert_ok!(SysManModule::revoke_sys_man(Origin::signed(1), 3u64, str2vec("reason")));
+
+		assert_eq!(SysManModule::children_of(&1u64), vec![2u64]);
+		assert_eq!(SysManModule::ancestors(&4u64), vec![2u64, 1u64]);
+		assert_eq!(SysManModule::ancestors(&3u64), vec![1u64]);
+		assert_eq!(SysManModule::ancestors(&1u64), Vec::<u64>::new());
+
+		for org_id in 10..15u64 {
+			assert_ok!(SysManModule::approve_org(Origin::signed(2), org_id, str2vec("{}"), None));
+		}
+		assert_ok!(SysManModule::revoke_org(Origin::signed(2), 12u64, str2vec("reason")));
+
+		// pages are capped at `MaxPageSize` and go on after the last org seen
+		let first = SysManModule::orgs_page(false, None, 10);
+		let last = first.last().map(|(id, _)| *id);
+
+		assert_eq!(first.len(), 3);
+
+		// revoking an org already seen does not shift the next page
+		let seen = first[0].0;
+		assert_ok!(SysManModule::revoke_org(Origin::signed(2), seen, str2vec("reason")));
+
+		let rest = SysManModule::orgs_page(false, last, 10);
+		let mut ids: Vec<u64> = first.iter().chain(rest.iter()).map(|(id, _)| *id).collect();
+		ids.sort();
+
+		assert_eq!(ids, vec![10u64, 11, 13, 14]);
+
+		let revoked: Vec<u64> =
+			SysManModule::orgs_page(true, None, 10).into_iter().map(|(id, _)| id).collect();
+
+		assert_eq!(revoked.len(), 2);
+		assert!(revoked.contains(&12u64) && revoked.contains(&seen));
+	});
+}
+
+#[test]
+fn sys_man_authority_should_follow_approvals() {
+	new_test_ext().execute_with(|| {
+		let root_authority = generate_test_account(Role::SysMan, Some(0), None, None);
+
+		SysMan::<Test>::insert(&1u64, root_authority);
+
+		assert_ok!(SysManModule::approve_sys_man(Origin::signed(1), 2u64, str2vec("{}"), None));
+		assert_ok!(SysManModule::approve_org(Origin::signed(1), 3u64, str2vec("{}"), None));
+
+		assert!(<SysManModule as SysManAuthority<u64>>::is_authority(&2u64));
+		assert!(SysManModule::has_role(&2u64, &Role::SysMan));
+		assert!(SysManModule::has_role(&3u64, &Role::Organization));
+		assert_eq!(false, SysManModule::has_role(&3u64, &Role::SysMan));
+		assert!(SysManModule::has_role(&4u64, &Role::User));
+
+		assert_ok!(SysManModule::suspend_sys_man(Origin::signed(1), 2u64, None));
+
+		assert_eq!(false, <SysManModule as SysManAuthority<u64>>::is_authority(&2u64));
+		assert_eq!(false, SysManModule::has_role(&2u64, &Role::SysMan));
+	});
+}
+
+#[test]
+fn migration_should_recount_accounts() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<SysManModule>();
+
+		// accounts stored before the counters and the org index existed
+		SysMan::<Test>::insert(&1u64, generate_test_account(Role::SysMan, Some(0), None, None));
+		SysMan::<Test>::insert(&2u64, generate_test_account(Role::SysMan, Some(1), Some(1), None));
+		Org::<Test>::insert(&10u64, generate_test_account(Role::Organization, None, Some(2), None));
+		Org::<Test>::insert(&11u64, generate_test_account(Role::Organization, None, Some(2), None));
+		Org::<Test>::insert(&12u64, generate_test_account(Role::Organization, None, None, None));
+
+		SysManModule::on_runtime_upgrade();
+
+		assert_eq!(SysManCnt::<Test>::get(), 2);
+		assert_eq!(OrgCnt::<Test>::get(), 3);
+		assert_eq!(SysManModule::org_cnt_by_sys_man(&2u64), 2);
+		assert_eq!(SysManModule::orgs_by_sys_man(&2u64, &10u64), Some(()));
+		assert_eq!(StorageVersion::get::<SysManModule>(), 1);
+
+		// running it again is a no-op
+		SysManCnt::<Test>::put(5);
+		SysManModule::on_runtime_upgrade();
+
+		assert_eq!(SysManCnt::<Test>::get(), 5);
+	});
+}
@@ -7,6 +7,8 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+pub mod traits;
+
 use frame_support::inherent::Vec;
 
 pub type TypeID = u32;
@@ -15,15 +17,20 @@ pub type String = Vec<u8>;
 
 #[frame_support::pallet]
 pub mod pallet {
+	use crate::traits::{OnAccountErased, OnAccountRebound};
 	use codec::{Decode, Encode};
 	use frame_support::pallet_prelude::*;
-	use frame_support::traits::Currency;
+	use frame_support::traits::{BalanceStatus, Currency, ReservableCurrency};
 	use frame_system as system;
+	use frame_system::pallet_prelude::*;
 
 	use scale_info::TypeInfo;
 	#[cfg(feature = "std")]
 	use serde::{Deserialize, Serialize};
-	use sp_runtime::RuntimeDebug;
+	use sp_runtime::{
+		traits::{Saturating, Zero},
+		RuntimeDebug,
+	};
 	use sp_std::prelude::*;
 
 	#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
@@ -57,6 +64,27 @@ pub mod pallet {
 		User,
 	}
 
+	/// Role of a staff account acting on behalf of an organization.
+	#[derive(
+		Encode, Decode, Ord, PartialOrd, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo,
+	)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	pub enum OrgRole {
+		Admin,
+		Issuer,
+		Verifier,
+	}
+
+	/// What a secondary key linked to an account may do on its behalf.
+	#[derive(
+		Encode, Decode, Ord, PartialOrd, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo,
+	)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	pub enum KeyPermission {
+		ReadOnly,
+		SubmitItems,
+	}
+
 	#[derive(Encode, Decode, Ord, PartialOrd, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
 	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 	pub enum Status {
@@ -136,7 +164,17 @@ pub mod pallet {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 		/// The currency mechanism.
-		type Currency: Currency<Self::AccountId>;
+		type Currency: ReservableCurrency<Self::AccountId>;
+		/// Amount reserved from an account while it holds a handle, so that handles can't be
+		/// squatted for free.
+		#[pallet::constant]
+		type HandleDeposit: Get<BalanceOf<Self>>;
+		/// Minimum length of a handle.
+		#[pallet::constant]
+		type MinHandleLen: Get<u32>;
+		/// Maximum length of a handle.
+		#[pallet::constant]
+		type MaxHandleLen: Get<u32>;
 	}
 
 	#[pallet::pallet]
@@ -151,6 +189,24 @@ pub mod pallet {
 	// https://docs.substrate.io/v3/runtime/storage#declaring-storage-items
 	pub type Something<T> = StorageValue<_, u32>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn account_by_handle)]
+	/// Owner of each claimed handle, for looking an account up by its handle.
+	pub type AccountByHandle<T: Config> =
+		StorageMap<_, Blake2_128Concat, crate::String, T::AccountId, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn handle_by_account)]
+	/// Handle claimed by each account.
+	pub type HandleByAccount<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, crate::String, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn handle_deposit)]
+	/// Deposit reserved from each account holding a handle.
+	pub type HandleDeposits<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
 	// Pallets use events to inform users when important changes are made.
 	// https://docs.substrate.io/v3/runtime/events-and-errors
 	#[pallet::event]
@@ -159,6 +215,12 @@ pub mod pallet {
 		/// Event documentation should end with an array that provides descriptive names for event
 		/// parameters. [something, who]
 		SomethingStored(u32, T::AccountId),
+		/// An account claimed a handle. [who, handle]
+		HandleClaimed(T::AccountId, crate::String),
+		/// An account released its handle. [who, handle]
+		HandleReleased(T::AccountId, crate::String),
+		/// A handle was transferred to another account. [from, to, handle]
+		HandleTransferred(T::AccountId, T::AccountId, crate::String),
 	}
 
 	// Errors inform users that something went wrong.
@@ -184,5 +246,147 @@ pub mod pallet {
 		HandleContainsInvalidChars,
 		/// Content type is `None`.
 		ContentIsEmpty,
+		/// Handle is already claimed by another account.
+		HandleIsNotAvailable,
+		/// Account already has a handle.
+		AccountAlreadyHasHandle,
+		/// Account has no handle.
+		AccountHasNoHandle,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Claim a unique handle for the signer's account, reserving `HandleDeposit` until it is
+		/// released. Handles are case-insensitive and are stored in lowercase.
+		#[pallet::weight(10_000)]
+		pub fn claim_handle(origin: OriginFor<T>, handle: crate::String) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let handle = Self::lowercase_and_validate_a_handle(handle)?;
+			ensure!(!<HandleByAccount<T>>::contains_key(&who), Error::<T>::AccountAlreadyHasHandle);
+			ensure!(!<AccountByHandle<T>>::contains_key(&handle), Error::<T>::HandleIsNotAvailable);
+			let deposit = T::HandleDeposit::get();
+			T::Currency::reserve(&who, deposit)?;
+
+			<AccountByHandle<T>>::insert(&handle, &who);
+			<HandleByAccount<T>>::insert(&who, &handle);
+			<HandleDeposits<T>>::insert(&who, deposit);
+
+			Self::deposit_event(Event::HandleClaimed(who, handle));
+			Ok(())
+		}
+
+		/// Release the signer's handle so that anyone can claim it, returning its deposit.
+		#[pallet::weight(10_000)]
+		pub fn release_handle(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let handle = <HandleByAccount<T>>::take(&who).ok_or(Error::<T>::AccountHasNoHandle)?;
+
+			<AccountByHandle<T>>::remove(&handle);
+			T::Currency::unreserve(&who, <HandleDeposits<T>>::take(&who));
+
+			Self::deposit_event(Event::HandleReleased(who, handle));
+			Ok(())
+		}
+
+		/// Transfer the signer's handle to `to`, which must not have a handle yet. The deposit
+		/// goes along with it and is returned to `to` when it releases the handle.
+		#[pallet::weight(10_000)]
+		pub fn transfer_handle(origin: OriginFor<T>, to: T::AccountId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let handle = <HandleByAccount<T>>::get(&who).ok_or(Error::<T>::AccountHasNoHandle)?;
+			ensure!(!<HandleByAccount<T>>::contains_key(&to), Error::<T>::AccountAlreadyHasHandle);
+			let deposit = <HandleDeposits<T>>::get(&who);
+			let missing =
+				T::Currency::repatriate_reserved(&who, &to, deposit, BalanceStatus::Reserved)?;
+
+			<HandleDeposits<T>>::remove(&who);
+			<HandleDeposits<T>>::insert(&to, deposit.saturating_sub(missing));
+			<HandleByAccount<T>>::remove(&who);
+			<HandleByAccount<T>>::insert(&to, &handle);
+			<AccountByHandle<T>>::insert(&handle, &to);
+
+			Self::deposit_event(Event::HandleTransferred(who, to, handle));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Lowercase a handle and check its length and that it only holds ASCII letters, digits,
+		/// `-` and `_`.
+		pub fn lowercase_and_validate_a_handle(
+			mut handle: crate::String,
+		) -> Result<crate::String, DispatchError> {
+			ensure!(handle.len() >= T::MinHandleLen::get() as usize, Error::<T>::HandleIsTooShort);
+			ensure!(handle.len() <= T::MaxHandleLen::get() as usize, Error::<T>::HandleIsTooLong);
+			ensure!(
+				handle.iter().all(|c| c.is_ascii_alphanumeric() || *c == b'-' || *c == b'_'),
+				Error::<T>::HandleContainsInvalidChars
+			);
+
+			handle.make_ascii_lowercase();
+			Ok(handle)
+		}
+
+		/// Account holding `handle` as typed by a user, e.g. `@Tokyo-Univ`. A leading `@` is
+		/// dropped and case is ignored.
+		pub fn lookup_handle(handle: &[u8]) -> Option<T::AccountId> {
+			let handle = handle.strip_prefix(b"@").unwrap_or(handle);
+			Self::account_by_handle(handle.to_ascii_lowercase())
+		}
+	}
+
+	impl<T: Config> OnAccountErased<T::AccountId> for Pallet<T> {
+		fn on_account_erased(who: &T::AccountId) -> Weight {
+			// the handle names the account, so it goes with it and becomes free to claim
+			match <HandleByAccount<T>>::take(who) {
+				Some(handle) => {
+					<AccountByHandle<T>>::remove(&handle);
+					T::Currency::unreserve(who, <HandleDeposits<T>>::take(who));
+					Self::deposit_event(Event::HandleReleased(who.clone(), handle));
+					T::DbWeight::get().reads_writes(3, 4)
+				},
+				None => T::DbWeight::get().reads(1),
+			}
+		}
+
+		fn max_erase_weight() -> Weight {
+			T::DbWeight::get().reads_writes(3, 4)
+		}
+	}
+
+	impl<T: Config> OnAccountRebound<T::AccountId> for Pallet<T> {
+		fn on_account_rebound(old: &T::AccountId, new: &T::AccountId) -> Weight {
+			// a new key that already holds a handle keeps it, the old one is left in place
+			if <HandleByAccount<T>>::contains_key(new) {
+				return T::DbWeight::get().reads(1)
+			}
+			match <HandleByAccount<T>>::take(old) {
+				Some(handle) => {
+					// the deposit goes along with the handle, if the new key can't take it the
+					// reserve stays with the old key
+					let deposit = <HandleDeposits<T>>::take(old);
+					let moved = T::Currency::repatriate_reserved(
+						old,
+						new,
+						deposit,
+						BalanceStatus::Reserved,
+					)
+					.map(|missing| deposit.saturating_sub(missing))
+					.unwrap_or_else(|_| Zero::zero());
+
+					<HandleDeposits<T>>::insert(new, moved);
+					<HandleByAccount<T>>::insert(new, &handle);
+					<AccountByHandle<T>>::insert(&handle, new);
+
+					Self::deposit_event(Event::HandleTransferred(old.clone(), new.clone(), handle));
+					T::DbWeight::get().reads_writes(5, 7)
+				},
+				None => T::DbWeight::get().reads(2),
+			}
+		}
+
+		fn max_rebound_weight() -> Weight {
+			T::DbWeight::get().reads_writes(5, 7)
+		}
 	}
 }
@@ -1,20 +1,157 @@
-use crate::{mock::*, Error};
+//! pallet-utils functionalities test
+use crate::{
+	mock::*,
+	traits::{OnAccountErased, OnAccountRebound},
+	Error,
+};
 use frame_support::{assert_noop, assert_ok};
 
+fn str2vec(s: &str) -> Vec<u8> {
+	s.as_bytes().to_vec()
+}
+
+#[test]
+fn handles_should_be_validated_and_looked_up_in_any_case() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			UtilsModule::claim_handle(Origin::signed(20), str2vec("ab")),
+			Error::<Test>::HandleIsTooShort
+		);
+		assert_noop!(
+			UtilsModule::claim_handle(Origin::signed(20), str2vec("a-very-long-handle")),
+			Error::<Test>::HandleIsTooLong
+		);
+		assert_noop!(
+			UtilsModule::claim_handle(Origin::signed(20), str2vec("tokyo univ")),
+			Error::<Test>::HandleContainsInvalidChars
+		);
+
+		assert_ok!(UtilsModule::claim_handle(Origin::signed(20), str2vec("Tokyo-Univ")));
+
+		System::assert_last_event(Event::UtilsModule(crate::Event::HandleClaimed(
+			20,
+			str2vec("tokyo-univ"),
+		)));
+		assert_eq!(UtilsModule::lookup_handle(b"@Tokyo-Univ"), Some(20));
+		assert_eq!(UtilsModule::lookup_handle(b"TOKYO-UNIV"), Some(20));
+		assert_eq!(UtilsModule::lookup_handle(b"@kyoto-univ"), None);
+		assert_noop!(
+			UtilsModule::claim_handle(Origin::signed(30), str2vec("tokyo-UNIV")),
+			Error::<Test>::HandleIsNotAvailable
+		);
+		assert_noop!(
+			UtilsModule::claim_handle(Origin::signed(20), str2vec("kyoto-univ")),
+			Error::<Test>::AccountAlreadyHasHandle
+		);
+	});
+}
+
+#[test]
+fn handles_should_hold_a_deposit_until_released() {
+	new_test_ext().execute_with(|| {
+		// accounts that can't afford the deposit can't claim a handle
+		assert_noop!(
+			UtilsModule::claim_handle(Origin::signed(40), str2vec("squatter")),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+
+		assert_ok!(UtilsModule::claim_handle(Origin::signed(20), str2vec("tokyo-univ")));
+
+		assert_eq!(Balances::reserved_balance(20), 10);
+		assert_eq!(Balances::free_balance(20), 90);
+		assert_eq!(UtilsModule::handle_deposit(20), 10);
+
+		assert_ok!(UtilsModule::release_handle(Origin::signed(20)));
+
+		assert_eq!(Balances::reserved_balance(20), 0);
+		assert_eq!(Balances::free_balance(20), 100);
+		assert_eq!(UtilsModule::lookup_handle(b"tokyo-univ"), None);
+		assert_noop!(
+			UtilsModule::release_handle(Origin::signed(20)),
+			Error::<Test>::AccountHasNoHandle
+		);
+
+		// released handles are free to claim again
+		assert_ok!(UtilsModule::claim_handle(Origin::signed(30), str2vec("tokyo-univ")));
+
+		assert_eq!(UtilsModule::lookup_handle(b"tokyo-univ"), Some(30));
+	});
+}
+
+#[test]
+fn transferred_handles_should_carry_their_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(UtilsModule::claim_handle(Origin::signed(20), str2vec("tokyo-univ")));
+		assert_ok!(UtilsModule::claim_handle(Origin::signed(30), str2vec("kyoto-univ")));
+
+		assert_noop!(
+			UtilsModule::transfer_handle(Origin::signed(20), 30),
+			Error::<Test>::AccountAlreadyHasHandle
+		);
+
+		assert_ok!(UtilsModule::release_handle(Origin::signed(30)));
+		assert_ok!(UtilsModule::transfer_handle(Origin::signed(20), 30));
+
+		System::assert_last_event(Event::UtilsModule(crate::Event::HandleTransferred(
+			20,
+			30,
+			str2vec("tokyo-univ"),
+		)));
+		assert_eq!(UtilsModule::lookup_handle(b"tokyo-univ"), Some(30));
+		assert_eq!(UtilsModule::handle_by_account(20), None);
+		assert_eq!(Balances::free_balance(20), 90);
+		assert_eq!(Balances::reserved_balance(20), 0);
+		assert_eq!(Balances::reserved_balance(30), 10);
+		assert_eq!(UtilsModule::handle_deposit(20), 0);
+		assert_eq!(UtilsModule::handle_deposit(30), 10);
+
+		// the new holder gets the deposit back on release
+		assert_ok!(UtilsModule::release_handle(Origin::signed(30)));
+
+		assert_eq!(Balances::free_balance(30), 110);
+	});
+}
+
 #[test]
-fn it_works_for_default_value() {
+fn erased_accounts_should_lose_their_handle() {
 	new_test_ext().execute_with(|| {
-		// Dispatch a signed extrinsic.
-		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
-		// Read pallet storage and assert an expected result.
-		assert_eq!(TemplateModule::something(), Some(42));
+		assert_ok!(UtilsModule::claim_handle(Origin::signed(20), str2vec("tokyo-univ")));
+
+		UtilsModule::on_account_erased(&20);
+
+		System::assert_last_event(Event::UtilsModule(crate::Event::HandleReleased(
+			20,
+			str2vec("tokyo-univ"),
+		)));
+		assert_eq!(UtilsModule::lookup_handle(b"tokyo-univ"), None);
+		assert_eq!(Balances::reserved_balance(20), 0);
+		assert_eq!(UtilsModule::handle_deposit(20), 0);
 	});
 }
 
 #[test]
-fn correct_error_for_none_value() {
+fn rebound_accounts_should_keep_their_handle() {
 	new_test_ext().execute_with(|| {
-		// Ensure the expected error is thrown when no value is present.
-		assert_noop!(TemplateModule::cause_error(Origin::signed(1)), Error::<Test>::NoneValue);
+		assert_ok!(UtilsModule::claim_handle(Origin::signed(20), str2vec("tokyo-univ")));
+		assert_ok!(UtilsModule::claim_handle(Origin::signed(30), str2vec("kyoto-univ")));
+
+		// a new key holding a handle of its own keeps it
+		UtilsModule::on_account_rebound(&20, &30);
+
+		assert_eq!(UtilsModule::lookup_handle(b"tokyo-univ"), Some(20));
+		assert_eq!(UtilsModule::lookup_handle(b"kyoto-univ"), Some(30));
+
+		UtilsModule::on_account_rebound(&20, &40);
+
+		System::assert_last_event(Event::UtilsModule(crate::Event::HandleTransferred(
+			20,
+			40,
+			str2vec("tokyo-univ"),
+		)));
+		assert_eq!(UtilsModule::lookup_handle(b"tokyo-univ"), Some(40));
+		assert_eq!(UtilsModule::handle_by_account(20), None);
+		assert_eq!(Balances::reserved_balance(20), 0);
+		assert_eq!(Balances::reserved_balance(40), 10);
+		assert_eq!(UtilsModule::handle_deposit(40), 10);
 	});
 }
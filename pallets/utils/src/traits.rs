@@ -0,0 +1,165 @@
+use crate::{KeyPermission, OrgRole, Role};
+use frame_support::weights::Weight;
+
+/// Resolves the staff accounts allowed to act on behalf of an organization.
+pub trait OrgMembership<AccountId: Clone> {
+	/// Key the organization registered as `org` goes by now, following key rotations.
+	fn current_org_key(org: &AccountId) -> AccountId;
+
+	/// Whether `org` is an approved organization that is currently active.
+	fn is_active_org(org: &AccountId) -> bool;
+
+	/// Role `who` holds in the active organization `org`, if any. The organization key itself
+	/// acts as an admin.
+	fn member_role(org: &AccountId, who: &AccountId) -> Option<OrgRole>;
+
+	/// Whether `who` acts for the active organization `org`.
+	fn is_member(org: &AccountId, who: &AccountId) -> bool {
+		Self::member_role(org, who).is_some()
+	}
+}
+
+/// Lets other pallets check sys-man approval without depending on the sys-man pallet.
+pub trait SysManAuthority<AccountId> {
+	/// Whether `who` is an active system manager that can exercise its authority.
+	fn is_authority(who: &AccountId) -> bool;
+
+	/// Whether `who` has been approved for `role` and is currently active in it.
+	fn has_role(who: &AccountId, role: &Role) -> bool;
+}
+
+impl<AccountId> SysManAuthority<AccountId> for () {
+	fn is_authority(_who: &AccountId) -> bool {
+		false
+	}
+
+	fn has_role(_who: &AccountId, role: &Role) -> bool {
+		*role == Role::User
+	}
+}
+
+/// Notifies pallets holding data linked to an account that its owner erased it.
+pub trait OnAccountErased<AccountId> {
+	/// Scrub or hide the personal data linked to `who`, returning the weight consumed.
+	fn on_account_erased(who: &AccountId) -> Weight;
+
+	/// Upper bound of the weight of `on_account_erased`, charged before the call.
+	fn max_erase_weight() -> Weight;
+}
+
+impl<AccountId> OnAccountErased<AccountId> for () {
+	fn on_account_erased(_who: &AccountId) -> Weight {
+		0
+	}
+
+	fn max_erase_weight() -> Weight {
+		0
+	}
+}
+
+impl<AccountId, A, B> OnAccountErased<AccountId> for (A, B)
+where
+	A: OnAccountErased<AccountId>,
+	B: OnAccountErased<AccountId>,
+{
+	fn on_account_erased(who: &AccountId) -> Weight {
+		A::on_account_erased(who).saturating_add(B::on_account_erased(who))
+	}
+
+	fn max_erase_weight() -> Weight {
+		A::max_erase_weight().saturating_add(B::max_erase_weight())
+	}
+}
+
+/// Notifies pallets holding data linked to an account that its owner deactivated or
+/// reactivated it.
+pub trait OnAccountStatusChange<AccountId> {
+	/// Hide the data linked to `who` until it is reactivated.
+	fn on_account_deactivated(who: &AccountId);
+
+	/// Show the data linked to `who` again.
+	fn on_account_reactivated(who: &AccountId);
+}
+
+impl<AccountId> OnAccountStatusChange<AccountId> for () {
+	fn on_account_deactivated(_who: &AccountId) {}
+
+	fn on_account_reactivated(_who: &AccountId) {}
+}
+
+/// Tells other pallets about the lifecycle of accounts without depending on the account pallet.
+pub trait AccountRegistry<AccountId> {
+	/// Whether the owner of `who` erased it, so no personal data may be linked to it anymore.
+	fn is_erased(who: &AccountId) -> bool;
+}
+
+impl<AccountId> AccountRegistry<AccountId> for () {
+	fn is_erased(_who: &AccountId) -> bool {
+		false
+	}
+}
+
+/// Resolves secondary signing keys to the account they act for.
+pub trait AccountResolver<AccountId: Clone> {
+	/// Primary account `key` is linked to and what it may do, `None` for keys that are not
+	/// linked.
+	fn linked_to(key: &AccountId) -> Option<(AccountId, KeyPermission)>;
+
+	/// Account `key` acts for. Keys that are not linked act for themselves with full permission.
+	fn resolve(key: &AccountId) -> (AccountId, KeyPermission) {
+		Self::linked_to(key).unwrap_or_else(|| (key.clone(), KeyPermission::SubmitItems))
+	}
+}
+
+impl<AccountId: Clone> AccountResolver<AccountId> for () {
+	fn linked_to(_key: &AccountId) -> Option<(AccountId, KeyPermission)> {
+		None
+	}
+}
+
+/// Notifies pallets holding data linked to an account that it moved to a new key.
+pub trait OnAccountRebound<AccountId> {
+	/// Move the data linked to `old` over to `new`, returning the weight consumed.
+	fn on_account_rebound(old: &AccountId, new: &AccountId) -> Weight;
+
+	/// Upper bound of the weight of `on_account_rebound`, charged before the call.
+	fn max_rebound_weight() -> Weight;
+}
+
+impl<AccountId> OnAccountRebound<AccountId> for () {
+	fn on_account_rebound(_old: &AccountId, _new: &AccountId) -> Weight {
+		0
+	}
+
+	fn max_rebound_weight() -> Weight {
+		0
+	}
+}
+
+impl<AccountId, A, B> OnAccountRebound<AccountId> for (A, B)
+where
+	A: OnAccountRebound<AccountId>,
+	B: OnAccountRebound<AccountId>,
+{
+	fn on_account_rebound(old: &AccountId, new: &AccountId) -> Weight {
+		A::on_account_rebound(old, new).saturating_add(B::on_account_rebound(old, new))
+	}
+
+	fn max_rebound_weight() -> Weight {
+		A::max_rebound_weight().saturating_add(B::max_rebound_weight())
+	}
+}
+
+impl<AccountId: Clone> OrgMembership<AccountId> for () {
+	fn current_org_key(org: &AccountId) -> AccountId {
+		org.clone()
+	}
+
+	fn is_active_org(_org: &AccountId) -> bool {
+		false
+	}
+
+	fn member_role(_org: &AccountId, _who: &AccountId) -> Option<OrgRole> {
+		None
+	}
+}
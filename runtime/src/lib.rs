@@ -97,10 +97,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 };
 
 /// This determines the average expected block time that we are targeting.
@@ -273,25 +273,100 @@ impl pallet_sudo::Config for Runtime {
 	type Call = Call;
 }
 
+parameter_types! {
+	pub const MinHandleLen: u32 = 3;
+	pub const MaxHandleLen: u32 = 32;
+	pub const HandleDeposit: Balance = 50_000;
+}
+
 impl pallet_utils::Config for Runtime {
 	type Event = Event;
 	type Currency = Balances;
+	type HandleDeposit = HandleDeposit;
+	type MinHandleLen = MinHandleLen;
+	type MaxHandleLen = MaxHandleLen;
+}
+
+parameter_types! {
+	pub const MinRecoveryDelay: BlockNumber = DAYS;
+	pub const MaxGuardians: u32 = 10;
+	pub const MaxLinkedKeys: u32 = 8;
 }
 
 impl pallet_account::Config for Runtime {
 	type Event = Event;
+	type SysManAuthority = SysMan;
+	type OnAccountErased = (Cv, Utils);
+	type OnAccountStatusChange = Cv;
+	type OnAccountRebound = (Cv, Certificate, Utils);
+	type MinRecoveryDelay = MinRecoveryDelay;
+	type MaxGuardians = MaxGuardians;
+	type MaxLinkedKeys = MaxLinkedKeys;
+}
+
+parameter_types! {
+	pub const MaxCascadeRevocations: u32 = 50;
+	pub const MaxDepth: u8 = 8;
+	pub const MaxChildren: u32 = 100;
+	pub const MaxChildrenLimit: u32 = 500;
+	pub const MaxOrgsPerSysMan: u32 = 500;
+	pub const ApprovalThreshold: u32 = 1;
+	pub const ProposalLifetime: BlockNumber = 7 * DAYS;
+	pub const MaxOrgMembers: u32 = 1_000;
+	pub const MaxMetadataLength: u32 = 2048;
+	pub const MaxDescriptionLength: u32 = 512;
+	pub const MaxPageSize: u32 = 100;
 }
 
 impl pallet_sys_man::Config for Runtime {
 	type Event = Event;
+	type MaxCascadeRevocations = MaxCascadeRevocations;
+	type MaxMetadataLength = MaxMetadataLength;
+	type MaxDescriptionLength = MaxDescriptionLength;
+	type MaxPageSize = MaxPageSize;
+	type MaxDepth = MaxDepth;
+	type MaxChildren = MaxChildren;
+	type MaxChildrenLimit = MaxChildrenLimit;
+	type MaxOrgsPerSysMan = MaxOrgsPerSysMan;
+	type ApprovalThreshold = ApprovalThreshold;
+	type ProposalLifetime = ProposalLifetime;
+	type MaxOrgMembers = MaxOrgMembers;
+	type UnixTime = Timestamp;
+}
+
+parameter_types! {
+	pub const MaxItemMetadataLength: u32 = 1_024;
+	pub const MaxItemFieldLength: u32 = 256;
+	pub const MaxExpiriesPerBlock: u32 = 100;
+	pub const MaxItemsPerSubject: u32 = 100;
+	pub const MaxItemsPerIssuer: u32 = 1_000;
+	pub const MaxItemsPerIssuerAndSubject: u32 = 10;
+	pub const MaxItemVersions: u32 = 10;
 }
 
 impl pallet_cv::Config for Runtime {
 	type Event = Event;
+	type AccountResolver = Account;
+	type AccountRegistry = Account;
+	type OrgMembership = SysMan;
+	type MaxItemFieldLength = MaxItemFieldLength;
+	type MaxItemMetadataLength = MaxItemMetadataLength;
+	type MaxExpiriesPerBlock = MaxExpiriesPerBlock;
+	type MaxItemsPerSubject = MaxItemsPerSubject;
+	type MaxItemsPerIssuer = MaxItemsPerIssuer;
+	type MaxItemsPerIssuerAndSubject = MaxItemsPerIssuerAndSubject;
+	type MaxItemVersions = MaxItemVersions;
+}
+
+parameter_types! {
+	pub const MaxCertificatesPerOrg: u32 = 1_000;
 }
 
 impl pallet_certificate::Config for Runtime {
 	type Event = Event;
+	type AccountResolver = Account;
+	type OrgMembership = SysMan;
+	type MaxCertificatesPerOrg = MaxCertificatesPerOrg;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -478,6 +553,72 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_sys_man_rpc_runtime_api::SysManApi<
+		Block,
+		AccountId,
+		pallet_sys_man::SysManAccount<Runtime>,
+		pallet_sys_man::AuditEntryOf<Runtime>,
+	> for Runtime {
+		fn account(
+			id: AccountId,
+			op_type: pallet_sys_man::OperationType,
+		) -> Option<pallet_sys_man::SysManAccount<Runtime>> {
+			match op_type {
+				pallet_sys_man::OperationType::SYS => SysMan::sys_man(id),
+				pallet_sys_man::OperationType::ORG => SysMan::org(id),
+			}
+		}
+
+		fn revoked_account(
+			id: AccountId,
+			op_type: pallet_sys_man::OperationType,
+		) -> Option<pallet_sys_man::SysManAccount<Runtime>> {
+			match op_type {
+				pallet_sys_man::OperationType::SYS => SysMan::sys_man_revoked(id),
+				pallet_sys_man::OperationType::ORG => SysMan::org_revoked(id),
+			}
+		}
+
+		fn children(id: AccountId) -> Vec<AccountId> {
+			SysMan::children_of(&id)
+		}
+
+		fn ancestors(id: AccountId) -> Vec<AccountId> {
+			SysMan::ancestors(&id)
+		}
+
+		fn subtree(id: AccountId, limit: u32) -> Vec<AccountId> {
+			SysMan::subtree(&id, limit)
+		}
+
+		fn orgs(
+			revoked: bool,
+			start_after: Option<AccountId>,
+			limit: u32,
+		) -> Vec<(AccountId, pallet_sys_man::SysManAccount<Runtime>)> {
+			SysMan::orgs_page(revoked, start_after, limit)
+		}
+
+		fn is_authority(id: AccountId) -> bool {
+			SysMan::get_authority(&id).is_ok()
+		}
+
+		fn audit_log(
+			start: pallet_sys_man::AuditId,
+			limit: u32,
+		) -> Vec<(pallet_sys_man::AuditId, pallet_sys_man::AuditEntryOf<Runtime>)> {
+			SysMan::audit_log_page(start, limit)
+		}
+
+		fn audit_log_of(
+			account: AccountId,
+			start: u32,
+			limit: u32,
+		) -> Vec<(pallet_sys_man::AuditId, pallet_sys_man::AuditEntryOf<Runtime>)> {
+			SysMan::audit_log_page_of(&account, start, limit)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (